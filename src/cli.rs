@@ -0,0 +1,330 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use thiserror::Error;
+
+use crate::{
+    export::{export_drills, pdf::CARDS_PER_PAGE, ExportError},
+    meyer_cross::{
+        analysis::DistributionReport,
+        defense::Defense,
+        fehler::FehlerTarget,
+        markov::{MarkovError, MarkovGenerator},
+        preparation::{Preparation, StreichenCount},
+        rules::DrillRules,
+        types::{Guard, Strike},
+        MeyerCross, MeyerCrossError,
+    },
+};
+
+pub const USAGE: &str = "\
+Usage:
+  meyers_cross [--remote-lan]        Start the app, --remote-lan opens the remote control
+                                     page to the network, with the remote feature.
+  meyers_cross export <FILE> [RULES] [--drills <N>]
+                                     Export drill cards as .svg, .png or a .pdf sheet.
+  meyers_cross stats [RULES] [--samples <N>] [--seed <N>]
+                                     Report how often the generator picks each strike,
+                                     opening, preparation and Fehler position.
+
+Rules:
+  --attacks <N|MIN-MAX>              Number of attacks, 2 to 8.
+  --strikes <long,short,flat,fehler> Allowed strikes.
+  --doppelfehler                     Allow consecutive Fehler.
+  --fehler-target <high-to-low|other-side|diagonal>
+                                     Where a Fehler strikes after the feint.
+  --guards <tag-left,ochs-right,...> Allowed guards as preparation.
+  --streichen <N,MIN-MAX,...>        Allowed Streichen counts as preparation, 1 to 8.
+  --left-handed                      Mirror the drills for left-handed fencers.
+  --guard-start                      Start the sequences plausibly from the guard.
+  --markov <FILE>                    Draw sequences from the transition matrix in FILE.
+  --defenses <parry,versetzen,counter-cut>
+                                     Give each attack a partner response.";
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("Unknown command {0}.")]
+    UnknownCommand(String),
+    #[error("Unknown option {0}.")]
+    UnknownOption(String),
+    #[error("Missing value for {0}.")]
+    MissingValue(String),
+    #[error("Invalid value {1} for {0}.")]
+    InvalidValue(String, String),
+    #[error("Missing output file.")]
+    MissingOutput,
+    #[error("{0} needs a build with the remote feature.")]
+    RemoteDisabled(String),
+    #[error(transparent)]
+    MeyerCross(#[from] MeyerCrossError),
+    #[error(transparent)]
+    Export(#[from] ExportError),
+    #[error(transparent)]
+    Markov(#[from] MarkovError),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Command {
+    Run {
+        remote_lan: bool,
+    },
+    Export {
+        output: PathBuf,
+        drills: usize,
+        rules: DrillRules,
+    },
+    Stats {
+        samples: usize,
+        seed: Option<u64>,
+        rules: DrillRules,
+    },
+}
+
+pub const DEFAULT_SAMPLES: usize = 10_000;
+
+/// Lowercase name with dashes, e.g. `tag-left` for `Guard::TagLeft`.
+fn slug(name: impl Display) -> String {
+    name.to_string().to_lowercase().replace(' ', "-")
+}
+
+fn parse_list<T: Copy + Display>(
+    option: &str,
+    value: &str,
+    candidates: &[T],
+) -> Result<Vec<T>, CliError> {
+    value
+        .split(',')
+        .map(|item| {
+            let invalid = || CliError::InvalidValue(option.to_string(), item.to_string());
+            if let Some(exact) = candidates.iter().find(|c| slug(c) == item) {
+                return Ok(*exact);
+            }
+            // The first word is enough where it is unambiguous, e.g. `long` for `long-edge`.
+            let mut matches = candidates
+                .iter()
+                .filter(|c| slug(c).split('-').next() == Some(item));
+            match (matches.next(), matches.next()) {
+                (Some(candidate), None) => Ok(*candidate),
+                _ => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
+fn parse_number(option: &str, value: &str) -> Result<usize, CliError> {
+    value
+        .parse()
+        .map_err(|_| CliError::InvalidValue(option.to_string(), value.to_string()))
+}
+
+fn next_value(option: &str, args: &mut dyn Iterator<Item = String>) -> Result<String, CliError> {
+    args.next()
+        .ok_or_else(|| CliError::MissingValue(option.to_string()))
+}
+
+/// Parses a rules option into `rules`, returns false if `option` is no rules option.
+fn parse_rules_option(
+    rules: &mut DrillRules,
+    option: &str,
+    args: &mut dyn Iterator<Item = String>,
+) -> Result<bool, CliError> {
+    match option {
+        "--attacks" => {
+            let value = next_value(option, args)?;
+            let (min, max) = value.split_once('-').unwrap_or((&value, &value));
+            rules.min_length = parse_number(option, min)?;
+            rules.max_length = parse_number(option, max)?;
+        }
+        "--strikes" => {
+            let value = next_value(option, args)?;
+            rules.strikes = parse_list(option, &value, &Strike::ALL)?
+                .into_iter()
+                .collect();
+        }
+        "--doppelfehler" => rules.doppelfehler_enabled = true,
+        "--fehler-target" => {
+            let value = next_value(option, args)?;
+            rules.fehler_target = match parse_list(option, &value, &FehlerTarget::ALL)?[..] {
+                [target] => target,
+                _ => return Err(CliError::InvalidValue(option.to_string(), value)),
+            };
+        }
+        "--defenses" => {
+            let value = next_value(option, args)?;
+            rules.defenses = parse_list(option, &value, &Defense::ALL)?
+                .into_iter()
+                .collect();
+        }
+        "--left-handed" => rules.left_handed = true,
+        "--guard-start" => rules.guard_start = true,
+        "--markov" => {
+            let value = next_value(option, args)?;
+            rules.markov = Some(MarkovGenerator::load(Path::new(&value))?);
+        }
+        "--guards" | "--streichen" => {
+            let value = next_value(option, args)?;
+            let preparations = if option == "--guards" {
+                parse_list(option, &value, &Guard::ALL)?
+                    .into_iter()
+                    .map(Preparation::Static)
+                    .collect::<Vec<_>>()
+            } else {
+                let mut preparations = vec![];
+                for counts in value.split(',') {
+                    let invalid = || CliError::InvalidValue(option.to_string(), counts.to_string());
+                    let (min, max) = counts.split_once('-').unwrap_or((counts, counts));
+                    let (min, max) = (parse_number(option, min)?, parse_number(option, max)?);
+                    if min > max {
+                        return Err(invalid());
+                    }
+                    for count in min..=max {
+                        let count = StreichenCount::new(count).map_err(|_| invalid())?;
+                        preparations.push(Preparation::Streichen(count));
+                    }
+                }
+                preparations
+            };
+            rules.preparations.extend(preparations);
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Parses all rules options, every other argument is passed on to `other`.
+fn parse_with_rules(
+    args: &mut dyn Iterator<Item = String>,
+    mut other: impl FnMut(String, &mut dyn Iterator<Item = String>) -> Result<(), CliError>,
+) -> Result<DrillRules, CliError> {
+    let mut rules = DrillRules {
+        preparations: Default::default(),
+        ..Default::default()
+    };
+    while let Some(arg) = args.next() {
+        if !parse_rules_option(&mut rules, &arg, args)? {
+            other(arg, args)?;
+        }
+    }
+    // The default preparation only applies if none is given explicitly.
+    if rules.preparations.is_empty() {
+        rules.preparations = DrillRules::default().preparations;
+    }
+    Ok(rules)
+}
+
+fn parse_run(args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut remote_lan = false;
+    for arg in args {
+        match arg.as_str() {
+            "--remote-lan" if cfg!(feature = "remote") => remote_lan = true,
+            "--remote-lan" => return Err(CliError::RemoteDisabled(arg)),
+            _ => return Err(CliError::UnknownOption(arg)),
+        }
+    }
+    Ok(Command::Run { remote_lan })
+}
+
+pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    match args.next().as_deref() {
+        None => parse_run(args),
+        Some(arg) if arg.starts_with("--") => {
+            parse_run(std::iter::once(arg.to_string()).chain(args))
+        }
+        Some("export") => {
+            let mut output = None;
+            let mut drills = CARDS_PER_PAGE;
+            let rules = parse_with_rules(&mut args, |arg, args| {
+                match arg.as_str() {
+                    "--drills" => drills = parse_number(&arg, &next_value(&arg, args)?)?,
+                    _ if arg.starts_with("--") => return Err(CliError::UnknownOption(arg)),
+                    _ => output = Some(PathBuf::from(arg)),
+                }
+                Ok(())
+            })?;
+            Ok(Command::Export {
+                output: output.ok_or(CliError::MissingOutput)?,
+                drills,
+                rules,
+            })
+        }
+        Some("stats") => {
+            let mut samples = DEFAULT_SAMPLES;
+            let mut seed = None;
+            let rules = parse_with_rules(&mut args, |arg, args| {
+                match arg.as_str() {
+                    "--samples" => samples = parse_number(&arg, &next_value(&arg, args)?)?,
+                    "--seed" => seed = Some(parse_number(&arg, &next_value(&arg, args)?)? as u64),
+                    _ => return Err(CliError::UnknownOption(arg)),
+                }
+                Ok(())
+            })?;
+            Ok(Command::Stats {
+                samples,
+                seed,
+                rules,
+            })
+        }
+        Some(command) => Err(CliError::UnknownCommand(command.to_string())),
+    }
+}
+
+pub fn export(output: PathBuf, drills: usize, rules: &DrillRules) -> Result<(), CliError> {
+    let mut rng = rand::thread_rng();
+    let drills = (0..drills.max(1))
+        .map(|_| MeyerCross::random(&mut rng, rules))
+        .collect::<Result<Vec<_>, _>>()?;
+    export_drills(&output, &drills)?;
+    Ok(())
+}
+
+pub fn stats(samples: usize, seed: Option<u64>, rules: &DrillRules) -> Result<(), CliError> {
+    let mut rng = StdRng::seed_from_u64(seed.unwrap_or_else(|| rand::thread_rng().gen()));
+    print!("{}", DistributionReport::sample(&mut rng, rules, samples)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ambiguous_short_names_are_rejected() {
+        let strikes = parse_list("--strikes", "long,fehler", &Strike::ALL).unwrap();
+        assert_eq!(strikes, vec![Strike::Long, Strike::Fehler]);
+        assert!(matches!(
+            parse_list("--guards", "tag", &Guard::ALL),
+            Err(CliError::InvalidValue(..))
+        ));
+        let guards = parse_list("--guards", "tag-left,alber", &Guard::ALL).unwrap();
+        assert_eq!(guards, vec![Guard::TagLeft, Guard::Alber]);
+    }
+
+    #[test]
+    fn reversed_streichen_ranges_are_rejected() {
+        let args = |streichen: &str| ["stats", "--streichen", streichen].map(String::from);
+        let Ok(Command::Stats { rules, .. }) = parse(args("2-4").into_iter()) else {
+            panic!("2-4 should parse");
+        };
+        assert_eq!(rules.preparations.len(), 3);
+        assert!(matches!(
+            parse(args("8-1").into_iter()),
+            Err(CliError::InvalidValue(..))
+        ));
+    }
+    #[test]
+    fn remote_lan_needs_the_remote_feature() {
+        let run = parse(["--remote-lan".to_string()].into_iter());
+        if cfg!(feature = "remote") {
+            assert_eq!(run.unwrap(), Command::Run { remote_lan: true });
+        } else {
+            assert!(matches!(run, Err(CliError::RemoteDisabled(..))));
+        }
+        assert!(matches!(
+            parse(["--remote".to_string()].into_iter()),
+            Err(CliError::UnknownOption(..))
+        ));
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    meyer_cross::{
+        types::{Opening, Strike},
+        MeyerCross,
+    },
+    render::{dashes, strike_letter, StrikeGlyph, FEHLER_SPLIT},
+};
+
+/// Card size in points, two by three cards fit on an A4 page.
+pub const CARD_WIDTH: f32 = 252.0;
+pub const CARD_HEIGHT: f32 = 245.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const GRAY: Rgb = Rgb(160, 160, 160);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Anchor {
+    Start,
+    Middle,
+}
+
+/// Drawing primitive in card coordinates, with the origin at the top left and y pointing down.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Primitive {
+    Line {
+        from: (f32, f32),
+        to: (f32, f32),
+        width: f32,
+        color: Rgb,
+    },
+    Rect {
+        origin: (f32, f32),
+        size: (f32, f32),
+        color: Rgb,
+    },
+    Text {
+        at: (f32, f32),
+        size: f32,
+        anchor: Anchor,
+        color: Rgb,
+        text: String,
+    },
+}
+
+/// Strike colors readable on white paper, the letters keep them apart in black and white.
+pub fn print_color(strike: Strike) -> Rgb {
+    match strike {
+        Strike::Flat => Rgb(200, 0, 0),
+        Strike::Long => Rgb::BLACK,
+        Strike::Short => Rgb(0, 90, 200),
+        Strike::Fehler => Rgb(0, 140, 60),
+    }
+}
+
+fn strike_label(number: usize, strike: Strike) -> String {
+    StrikeGlyph::Letter.label(number, strike)
+}
+
+/// A dashed line toward the feint turning into a solid one toward the real target.
+fn fehler_path(center: (f32, f32), feint: (f32, f32), target: (f32, f32)) -> Vec<Primitive> {
+    let lerp =
+        |a: (f32, f32), b: (f32, f32), t: f32| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+    let color = print_color(Strike::Fehler);
+    let split = lerp(center, feint, FEHLER_SPLIT);
+    let length = (split.0 - center.0).hypot(split.1 - center.1);
+    let mut lines = dashes(length, 6.0)
+        .into_iter()
+        .map(|(start, end)| Primitive::Line {
+            from: lerp(center, split, start),
+            to: lerp(center, split, end),
+            width: 1.5,
+            color,
+        })
+        .collect::<Vec<_>>();
+    lines.push(Primitive::Line {
+        from: split,
+        to: lerp(split, target, 0.8),
+        width: 1.5,
+        color,
+    });
+    lines
+}
+
+pub fn card_primitives(drill: &MeyerCross) -> Vec<Primitive> {
+    let mut primitives = vec![Primitive::Rect {
+        origin: (0.0, 0.0),
+        size: (CARD_WIDTH, CARD_HEIGHT),
+        color: Rgb::GRAY,
+    }];
+
+    primitives.push(Primitive::Text {
+        at: (12.0, 24.0),
+        size: 14.0,
+        anchor: Anchor::Start,
+        color: Rgb::BLACK,
+        text: drill.preparation.map_or("No preparation".to_string(), |p| {
+            format!("Preparation: {p}")
+        }),
+    });
+
+    let (left, top, size) = (12.0, 36.0, 150.0);
+    for (from, to) in [
+        ((left, top), (left + size, top + size)),
+        ((left + size, top), (left, top + size)),
+    ] {
+        primitives.push(Primitive::Line {
+            from,
+            to,
+            width: 1.5,
+            color: Rgb::GRAY,
+        });
+    }
+
+    let center = (left + size / 2.0, top + size / 2.0);
+    let dist = 0.35 * size;
+    let font_size = 20.0;
+    let attacks = drill.attack_sequence.as_deref().unwrap_or_default();
+    for (index, attack) in attacks.iter().enumerate() {
+        let number = index + 1;
+        let factor = if number > 4 { 0.5 } else { 1.0 };
+        let position = |opening: Opening| {
+            let (dx, dy) = match opening {
+                Opening::TopLeft => (-1.0, -1.0),
+                Opening::TopRight => (1.0, -1.0),
+                Opening::BottomLeft => (-1.0, 1.0),
+                Opening::BottomRight => (1.0, 1.0),
+            };
+            (center.0 + dx * dist * factor, center.1 + dy * dist * factor)
+        };
+        let (x, y) = position(attack.opening);
+        if let Some(target) = attack.target {
+            primitives.extend(fehler_path(center, (x, y), position(target)));
+        }
+        primitives.push(Primitive::Text {
+            at: (x, y + 0.35 * font_size),
+            size: font_size,
+            anchor: Anchor::Middle,
+            color: print_color(attack.strike),
+            text: strike_label(number, attack.strike),
+        });
+
+        primitives.push(Primitive::Text {
+            at: (168.0, 52.0 + index as f32 * 16.0),
+            size: 10.0,
+            anchor: Anchor::Start,
+            color: print_color(attack.strike),
+            text: {
+                let text = match attack.target {
+                    Some(target) => format!(
+                        "{} {} -> {target}",
+                        strike_label(number, attack.strike),
+                        attack.opening
+                    ),
+                    None => format!("{} {}", strike_label(number, attack.strike), attack.opening),
+                };
+                match attack.response {
+                    Some(response) => format!("{text} / {response}"),
+                    None => text,
+                }
+            },
+        });
+    }
+
+    for (row, strikes) in Strike::ALL.chunks(2).enumerate() {
+        for (column, &strike) in strikes.iter().enumerate() {
+            primitives.push(Primitive::Text {
+                at: (12.0 + column as f32 * 120.0, 208.0 + row as f32 * 16.0),
+                size: 10.0,
+                anchor: Anchor::Start,
+                color: print_color(strike),
+                text: format!("{} {strike}", strike_letter(strike)),
+            });
+        }
+    }
+
+    primitives
+}
@@ -0,0 +1,53 @@
+pub mod card;
+pub mod pdf;
+pub mod png;
+pub mod svg;
+
+use std::{fs, path::Path};
+
+use thiserror::Error;
+
+use crate::meyer_cross::MeyerCross;
+
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Could not render PNG: {0}")]
+    Png(String),
+    #[error("Unsupported export format, use .svg, .png or .pdf.")]
+    UnsupportedFormat,
+    #[error("No drill to export.")]
+    NoDrill,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExportFormat {
+    Svg,
+    Png,
+    Pdf,
+}
+
+impl ExportFormat {
+    pub fn from_path(path: &Path) -> Result<Self, ExportError> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("svg") => Ok(ExportFormat::Svg),
+            Some("png") => Ok(ExportFormat::Png),
+            Some("pdf") => Ok(ExportFormat::Pdf),
+            _ => Err(ExportError::UnsupportedFormat),
+        }
+    }
+}
+
+/// Writes the first drill as SVG or PNG card, or all drills as PDF sheet, depending on the
+/// file extension.
+pub fn export_drills(path: &Path, drills: &[MeyerCross]) -> Result<(), ExportError> {
+    let format = ExportFormat::from_path(path)?;
+    let first = drills.first().ok_or(ExportError::NoDrill)?;
+    match format {
+        ExportFormat::Svg => fs::write(path, svg::render_card(first))?,
+        ExportFormat::Png => fs::write(path, png::render_card(first)?)?,
+        ExportFormat::Pdf => fs::write(path, pdf::render_sheet(drills))?,
+    }
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use std::fmt::Write;
+
+use crate::meyer_cross::MeyerCross;
+
+use super::card::{card_primitives, Anchor, Primitive, Rgb, CARD_HEIGHT, CARD_WIDTH};
+
+pub const A4_WIDTH: f32 = 595.0;
+pub const A4_HEIGHT: f32 = 842.0;
+const COLUMNS: usize = 2;
+const ROWS: usize = 3;
+pub const CARDS_PER_PAGE: usize = COLUMNS * ROWS;
+const MARGIN: f32 = 36.0;
+const GAP: f32 = 18.0;
+/// Average Helvetica glyph width per font size, good enough to center short labels.
+const AVERAGE_GLYPH_WIDTH: f32 = 0.55;
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('(', "\\(")
+        .replace(')', "\\)")
+}
+
+fn color(Rgb(r, g, b): Rgb) -> String {
+    format!(
+        "{:.3} {:.3} {:.3}",
+        r as f32 / 255.0,
+        g as f32 / 255.0,
+        b as f32 / 255.0
+    )
+}
+
+/// Content stream drawing one card with its top left corner at `origin` in page coordinates.
+fn card_content(content: &mut String, drill: &MeyerCross, origin: (f32, f32)) {
+    // PDF pages have their origin at the bottom left.
+    let at = |(x, y): (f32, f32)| (origin.0 + x, A4_HEIGHT - origin.1 - y);
+
+    for primitive in card_primitives(drill) {
+        // Writing into a String cannot fail.
+        let _ = match primitive {
+            Primitive::Line {
+                from,
+                to,
+                width,
+                color: c,
+            } => {
+                let (from, to) = (at(from), at(to));
+                writeln!(
+                    content,
+                    "{} RG {width} w {} {} m {} {} l S",
+                    color(c),
+                    from.0,
+                    from.1,
+                    to.0,
+                    to.1
+                )
+            }
+            Primitive::Rect {
+                origin: rect_origin,
+                size,
+                color: c,
+            } => {
+                let bottom_left = at((rect_origin.0, rect_origin.1 + size.1));
+                writeln!(
+                    content,
+                    "{} RG 1 w {} {} {} {} re S",
+                    color(c),
+                    bottom_left.0,
+                    bottom_left.1,
+                    size.0,
+                    size.1
+                )
+            }
+            Primitive::Text {
+                at: text_at,
+                size,
+                anchor,
+                color: c,
+                text,
+            } => {
+                let (mut x, y) = at(text_at);
+                if anchor == Anchor::Middle {
+                    x -= text.chars().count() as f32 * AVERAGE_GLYPH_WIDTH * size / 2.0;
+                }
+                writeln!(
+                    content,
+                    "BT /F1 {size} Tf {} rg {x} {y} Td ({}) Tj ET",
+                    color(c),
+                    escape(&text)
+                )
+            }
+        };
+    }
+}
+
+/// A4 sheet with up to six drill cards per page.
+pub fn render_sheet(drills: &[MeyerCross]) -> Vec<u8> {
+    let pages = drills.chunks(CARDS_PER_PAGE).collect::<Vec<_>>();
+    let page_count = pages.len().max(1);
+
+    // Object numbers: 1 catalog, 2 page tree, 3 font, then a page and its content per page.
+    let page_object = |page: usize| 4 + 2 * page;
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {page_count} >>",
+            (0..page_count)
+                .map(|p| format!("{} 0 R", page_object(p)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    ];
+
+    for page in 0..page_count {
+        let mut content = String::new();
+        for (index, drill) in pages
+            .get(page)
+            .copied()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+        {
+            let (column, row) = (index % COLUMNS, index / COLUMNS);
+            let origin = (
+                MARGIN + column as f32 * (CARD_WIDTH + GAP),
+                MARGIN + row as f32 * (CARD_HEIGHT + GAP),
+            );
+            card_content(&mut content, drill, origin);
+        }
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {A4_WIDTH} {A4_HEIGHT}] \
+             /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            page_object(page) + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{content}endstream",
+            content.len()
+        ));
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = vec![];
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n{object}\nendobj\n", index + 1).bytes());
+    }
+    let xref_offset = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).bytes());
+    for offset in offsets {
+        pdf.extend(format!("{offset:010} 00000 n \n").bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .bytes(),
+    );
+    pdf
+}
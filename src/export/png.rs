@@ -0,0 +1,33 @@
+use resvg::{tiny_skia, usvg};
+
+use crate::meyer_cross::MeyerCross;
+
+use super::{svg, ExportError};
+
+/// Rendering scale over the SVG's own pixel size, about 400 dpi when printed at card size.
+pub const SCALE: f32 = 4.0;
+
+pub fn render_card(drill: &MeyerCross) -> Result<Vec<u8>, ExportError> {
+    let mut options = usvg::Options {
+        font_family: svg::FONT_FAMILY.to_string(),
+        ..Default::default()
+    };
+    options
+        .fontdb_mut()
+        .load_font_data(include_bytes!("../../assets/OpenSans-Regular.ttf").to_vec());
+
+    let tree = usvg::Tree::from_str(&svg::render_card(drill), &options)
+        .map_err(|e| ExportError::Png(e.to_string()))?;
+    let size = tree.size().to_int_size().scale_by(SCALE);
+    let mut pixmap = size
+        .and_then(|size| tiny_skia::Pixmap::new(size.width(), size.height()))
+        .ok_or_else(|| ExportError::Png("empty image".to_string()))?;
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(SCALE, SCALE),
+        &mut pixmap.as_mut(),
+    );
+    pixmap
+        .encode_png()
+        .map_err(|e| ExportError::Png(e.to_string()))
+}
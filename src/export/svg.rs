@@ -0,0 +1,78 @@
+use std::fmt::Write;
+
+use crate::meyer_cross::MeyerCross;
+
+use super::card::{card_primitives, Anchor, Primitive, Rgb, CARD_HEIGHT, CARD_WIDTH};
+
+pub const FONT_FAMILY: &str = "Open Sans";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn color(Rgb(r, g, b): Rgb) -> String {
+    format!("rgb({r},{g},{b})")
+}
+
+pub fn render_card(drill: &MeyerCross) -> String {
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{CARD_WIDTH}pt" height="{CARD_HEIGHT}pt" viewBox="0 0 {CARD_WIDTH} {CARD_HEIGHT}">"#
+    );
+    svg.push_str(r#"<rect width="100%" height="100%" fill="white"/>"#);
+
+    for primitive in card_primitives(drill) {
+        // Writing into a String cannot fail.
+        let _ = match primitive {
+            Primitive::Line {
+                from,
+                to,
+                width,
+                color: c,
+            } => write!(
+                svg,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{width}"/>"#,
+                from.0,
+                from.1,
+                to.0,
+                to.1,
+                color(c)
+            ),
+            Primitive::Rect {
+                origin,
+                size,
+                color: c,
+            } => write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{}"/>"#,
+                origin.0,
+                origin.1,
+                size.0,
+                size.1,
+                color(c)
+            ),
+            Primitive::Text {
+                at,
+                size,
+                anchor,
+                color: c,
+                text,
+            } => write!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="{FONT_FAMILY}, Helvetica, sans-serif" font-size="{size}" text-anchor="{}" fill="{}">{}</text>"#,
+                at.0,
+                at.1,
+                match anchor {
+                    Anchor::Start => "start",
+                    Anchor::Middle => "middle",
+                },
+                color(c),
+                escape(&text)
+            ),
+        };
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
@@ -0,0 +1,514 @@
+use std::fmt;
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    meyer_cross::{
+        attack_sequence::AttackSequenceLength,
+        mirror::Mirror,
+        preparation::Preparation,
+        types::{Opening, Strike},
+    },
+    render::{dashes, FEHLER_SPLIT},
+    resources::{countdown::Countdown, flash::Flash, meyer_cross::MeyerCross},
+};
+
+use super::{fencer_window::FencerWindow, setup_egui::UiState};
+
+pub use crate::render::{strike_letter, StrikeGlyph};
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum Palette {
+    #[default]
+    Classic,
+    ColorBlindSafe,
+    HighContrast,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 3] = [
+        Palette::Classic,
+        Palette::ColorBlindSafe,
+        Palette::HighContrast,
+    ];
+}
+
+impl fmt::Display for Palette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Palette::Classic => "Classic",
+            Palette::ColorBlindSafe => "Color-Blind Safe",
+            Palette::HighContrast => "High Contrast",
+        })
+    }
+}
+
+pub fn strike_color(strike: Strike, palette: Palette) -> Color {
+    match palette {
+        Palette::Classic => match strike {
+            Strike::Flat => Color::RED,
+            Strike::Long => Color::WHITE,
+            Strike::Short => Color::BLUE,
+            Strike::Fehler => Color::GREEN,
+        },
+        // Okabe-Ito colors, distinguishable with the common color vision deficiencies.
+        Palette::ColorBlindSafe => match strike {
+            Strike::Flat => Color::rgb(0.902, 0.624, 0.0),
+            Strike::Long => Color::WHITE,
+            Strike::Short => Color::rgb(0.337, 0.706, 0.914),
+            Strike::Fehler => Color::rgb(0.8, 0.475, 0.655),
+        },
+        Palette::HighContrast => match strike {
+            Strike::Flat => Color::CYAN,
+            Strike::Long => Color::WHITE,
+            Strike::Short => Color::YELLOW,
+            Strike::Fehler => Color::FUCHSIA,
+        },
+    }
+}
+
+#[derive(Component)]
+pub struct AttackIndicator(pub usize);
+
+#[derive(Component)]
+pub struct PreparationIndicator;
+
+/// Placement of the cross, scaled to the window so it neither clips nor gets lost.
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+pub struct CrossLayout {
+    pub center: Vec2,
+    pub dist: f32,
+    pub font_size: f32,
+    pub preparation_position: Vec2,
+    pub preparation_font_size: f32,
+    /// Center of the first sequence strip row, further rows follow below.
+    pub strip_position: Vec2,
+    pub strip_cell: f32,
+    /// Center of the partner's cross when the screen is split for partner responses.
+    pub partner_center: Option<Vec2>,
+}
+
+impl Default for CrossLayout {
+    fn default() -> Self {
+        Self::from_window_size(1280.0, 720.0, 0)
+    }
+}
+
+impl CrossLayout {
+    const DIST_PER_SIZE: f32 = 250.0 / 720.0;
+    const FONT_PER_SIZE: f32 = 150.0 / 720.0;
+    const PREPARATION_FONT_PER_SIZE: f32 = 50.0 / 720.0;
+    const STRIP_CELL_PER_HEIGHT: f32 = 0.1;
+    /// Width of a strip cell relative to its height.
+    pub const STRIP_CELL_ASPECT: f32 = 1.5;
+
+    /// Landscape windows center the cross with the preparation in its middle. Portrait
+    /// windows, e.g. phones, move the cross up and show the preparation below it.
+    /// `strip_rows` sequence strip rows are reserved at the bottom.
+    pub fn from_window_size(width: f32, height: f32, strip_rows: usize) -> Self {
+        let max_cells = AttackSequenceLength::MAX_LENGTH as f32 * Self::STRIP_CELL_ASPECT + 1.0;
+        let strip_cell = (Self::STRIP_CELL_PER_HEIGHT * height).min(width / max_cells);
+        let strip_height = strip_cell * strip_rows as f32;
+        let strip_position =
+            Vec2::new(0.0, (strip_cell - height) / 2.0 + strip_height - strip_cell);
+
+        let cross_height = height - strip_height;
+        let size = width.min(cross_height);
+        let dist = Self::DIST_PER_SIZE * size;
+        let font_size = Self::FONT_PER_SIZE * size;
+        let preparation_font_size = Self::PREPARATION_FONT_PER_SIZE * size;
+
+        if cross_height > width {
+            let center = Vec2::new(0.0, (cross_height - width) / 4.0 + strip_height / 2.0);
+            CrossLayout {
+                center,
+                dist,
+                font_size,
+                preparation_position: center - Vec2::new(0.0, dist + font_size),
+                preparation_font_size: preparation_font_size * 1.5,
+                strip_position,
+                strip_cell,
+                partner_center: None,
+            }
+        } else {
+            let center = Vec2::new(0.0, strip_height / 2.0);
+            CrossLayout {
+                center,
+                dist,
+                font_size,
+                preparation_position: center,
+                preparation_font_size,
+                strip_position,
+                strip_cell,
+                partner_center: None,
+            }
+        }
+    }
+
+    /// Splits the window in two halves, the attacker's cross on the left and the partner's on
+    /// the right. The sequence strip still spans the whole width.
+    pub fn split(width: f32, height: f32, strip_rows: usize) -> Self {
+        let whole = Self::from_window_size(width, height, strip_rows);
+        let half = Self::from_window_size(width / 2.0, height, strip_rows);
+        let offset = Vec2::new(width / 4.0, 0.0);
+        CrossLayout {
+            center: half.center - offset,
+            preparation_position: half.preparation_position - offset,
+            strip_position: whole.strip_position,
+            strip_cell: whole.strip_cell,
+            partner_center: Some(half.center + offset),
+            ..half
+        }
+    }
+
+    /// Center of the strip cell showing the attack at `index` of a sequence of `count`.
+    pub fn strip_cell_position(&self, row: usize, index: usize, count: usize) -> Vec2 {
+        let offset = index as f32 - (count as f32 - 1.0) / 2.0;
+        self.strip_position
+            + Vec2::new(
+                offset * self.strip_cell * Self::STRIP_CELL_ASPECT,
+                -(row as f32) * self.strip_cell,
+            )
+    }
+
+    /// Position of an attack indicator, the attacks after the first four move inside.
+    pub fn attack_position(&self, number: usize, opening: Opening) -> Vec3 {
+        let inner_pos_factor = if number > 4 { 0.5 } else { 1.0 };
+        (self.center + opening_direction(opening) * self.dist * inner_pos_factor).extend(0.0)
+    }
+
+    /// Position of a response indicator on the partner's cross, if the screen is split.
+    pub fn partner_position(&self, number: usize, opening: Opening) -> Option<Vec3> {
+        let partner = CrossLayout {
+            center: self.partner_center?,
+            ..*self
+        };
+        Some(partner.attack_position(number, opening))
+    }
+}
+
+/// Diagonal pointing from the center of the cross toward the opening.
+pub fn opening_direction(opening: Opening) -> Vec2 {
+    match opening {
+        Opening::TopLeft => Vec2::new(-1.0, 1.0),
+        Opening::TopRight => Vec2::new(1.0, 1.0),
+        Opening::BottomLeft => Vec2::new(-1.0, -1.0),
+        Opening::BottomRight => Vec2::new(1.0, -1.0),
+    }
+}
+
+pub fn spawn_indicators(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("OpenSans-Regular.ttf");
+    let layout = CrossLayout::default();
+    let text_style = TextStyle {
+        font,
+        font_size: layout.font_size,
+        color: Color::WHITE,
+    };
+
+    for id in 1..=8 {
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(id.to_string(), text_style.clone())
+                    .with_alignment(TextAlignment::Center),
+                ..default()
+            },
+            AttackIndicator(id),
+        ));
+    }
+
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: layout.preparation_font_size,
+                    ..text_style
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            ..default()
+        },
+        PreparationIndicator,
+    ));
+}
+
+/// Lays the cross out for the fencer window if there is one, otherwise for the primary window.
+pub fn update_cross_layout(
+    ui: Res<UiState>,
+    fencer_windows: Query<&Window, With<FencerWindow>>,
+    primary_windows: Query<&Window, With<PrimaryWindow>>,
+    mut layout: ResMut<CrossLayout>,
+) {
+    let window = fencer_windows
+        .get_single()
+        .or_else(|_| primary_windows.get_single());
+    if let Ok(window) = window {
+        let strip_rows = match (ui.preview_strip, ui.queue_next_drill) {
+            (false, _) => 0,
+            (true, false) => 1,
+            (true, true) => 2,
+        };
+        let layout_for = if ui.partner_drill {
+            CrossLayout::split
+        } else {
+            CrossLayout::from_window_size
+        };
+        layout.set_if_neq(layout_for(window.width(), window.height(), strip_rows));
+    }
+}
+
+pub fn position_attack_indicators(
+    meyer_cross: Res<MeyerCross>,
+    countdown: Res<Countdown>,
+    flash: Res<Flash>,
+    ui: Res<UiState>,
+    layout: Res<CrossLayout>,
+    mut query: Query<(&mut Transform, &mut Text, &mut Visibility, &AttackIndicator)>,
+) {
+    if let Some(attack_sequence) = meyer_cross.attack_sequence.as_ref() {
+        for (mut transform, mut text, mut visibility, attack_indicator) in query.iter_mut() {
+            let index = attack_indicator.0 - 1;
+            if let Some(attack) = attack_sequence
+                .get(index)
+                .filter(|_| countdown.is_revealed(index) && !flash.is_hidden())
+            {
+                *visibility = Visibility::Visible;
+                transform.translation = layout.attack_position(
+                    attack_indicator.0,
+                    attack.opening.mirrored_if(ui.partner_perspective),
+                );
+                let section = &mut text.sections[0];
+                section.value = ui.strike_glyph.label(attack_indicator.0, attack.strike);
+                section.style.color = strike_color(attack.strike, ui.palette);
+                section.style.font_size = layout.font_size;
+            } else {
+                *visibility = Visibility::Hidden;
+            }
+        }
+    }
+}
+
+/// Draws each revealed Fehler as a dashed feint splitting into the solid real strike.
+pub fn draw_fehler_paths(
+    meyer_cross: Res<MeyerCross>,
+    countdown: Res<Countdown>,
+    flash: Res<Flash>,
+    ui: Res<UiState>,
+    layout: Res<CrossLayout>,
+    mut gizmos: Gizmos,
+) {
+    if flash.is_hidden() {
+        return;
+    }
+    let attacks = meyer_cross.attack_sequence.as_deref().unwrap_or_default();
+    for (index, attack) in attacks.iter().enumerate() {
+        let Some(target) = attack.target.filter(|_| countdown.is_revealed(index)) else {
+            continue;
+        };
+        let number = index + 1;
+        let color = strike_color(attack.strike, ui.palette);
+        let position = |opening: Opening| {
+            layout
+                .attack_position(number, opening.mirrored_if(ui.partner_perspective))
+                .truncate()
+        };
+        let split = layout.center.lerp(position(attack.opening), FEHLER_SPLIT);
+        for (start, end) in dashes(split.distance(layout.center), 0.1 * layout.dist) {
+            gizmos.line_2d(
+                layout.center.lerp(split, start),
+                layout.center.lerp(split, end),
+                color,
+            );
+        }
+        // Ends short of the target so the indicator stays readable.
+        gizmos.line_2d(split, split.lerp(position(target), 0.8), color);
+    }
+}
+
+pub fn update_preparation_indicator(
+    meyer_cross: Res<MeyerCross>,
+    countdown: Res<Countdown>,
+    flash: Res<Flash>,
+    layout: Res<CrossLayout>,
+    mut query: Query<(&mut Transform, &mut Text, &mut Visibility), With<PreparationIndicator>>,
+) {
+    // Streichen are counted out one by one while the timer runs the preparation.
+    let label = match meyer_cross.preparation {
+        Some(Preparation::Streichen(count)) => {
+            countdown.streichen_stroke(count.into()).map_or_else(
+                || Preparation::Streichen(count).to_string(),
+                |stroke| format!("Streichen {stroke} of {}", usize::from(count)),
+            )
+        }
+        preparation => preparation.map_or(String::new(), |p| p.to_string()),
+    };
+    for (mut transform, mut text, mut visibility) in query.iter_mut() {
+        if text.sections[0].value != label {
+            text.sections[0].value = label.clone();
+        }
+        if layout.is_changed() {
+            text.sections[0].style.font_size = layout.preparation_font_size;
+            transform.translation = layout.preparation_position.extend(0.0);
+        }
+        *visibility = if flash.is_hidden() {
+            Visibility::Hidden
+        } else {
+            Visibility::Visible
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        meyer_cross::{preparation::StreichenCount, types::Attack},
+        resources::countdown::CueTiming,
+    };
+
+    fn drill(attacks: &[(Opening, Strike)]) -> MeyerCross {
+        let mut drill = MeyerCross::new();
+        drill.attack_sequence = Some(
+            attacks
+                .iter()
+                .map(|&(opening, strike)| Attack::new(opening, strike))
+                .collect(),
+        );
+        drill
+    }
+
+    fn indicator_app(meyer_cross: MeyerCross, ui: UiState) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(meyer_cross)
+            .insert_resource(Countdown::default())
+            .insert_resource(Flash::default())
+            .insert_resource(ui)
+            .insert_resource(CrossLayout::default())
+            .add_systems(Update, position_attack_indicators);
+        for id in 1..=8 {
+            app.world.spawn((
+                Transform::default(),
+                Text::from_section("", TextStyle::default()),
+                Visibility::Hidden,
+                AttackIndicator(id),
+            ));
+        }
+        app.update();
+        app
+    }
+
+    /// Indicator state by attack number.
+    fn indicators(app: &mut App) -> Vec<(usize, Vec3, Color, Visibility)> {
+        let mut indicators = app
+            .world
+            .query::<(&Transform, &Text, &Visibility, &AttackIndicator)>()
+            .iter(&app.world)
+            .map(|(transform, text, visibility, indicator)| {
+                (
+                    indicator.0,
+                    transform.translation,
+                    text.sections[0].style.color,
+                    *visibility,
+                )
+            })
+            .collect::<Vec<_>>();
+        indicators.sort_by_key(|(number, ..)| *number);
+        indicators
+    }
+
+    #[test]
+    fn visibility_matches_sequence_length() {
+        for length in AttackSequenceLength::MIN_LENGTH..=AttackSequenceLength::MAX_LENGTH {
+            let attacks = vec![(Opening::TopLeft, Strike::Long); length];
+            let mut app = indicator_app(drill(&attacks), UiState::new());
+            for (number, _, _, visibility) in indicators(&mut app) {
+                let expected = if number <= length {
+                    Visibility::Visible
+                } else {
+                    Visibility::Hidden
+                };
+                assert_eq!(visibility, expected, "attack {number} of {length}");
+            }
+        }
+    }
+
+    #[test]
+    fn color_matches_strike() {
+        let attacks = Strike::ALL.map(|strike| (Opening::TopRight, strike));
+        for palette in Palette::ALL {
+            let mut ui = UiState::new();
+            ui.palette = palette;
+            let mut app = indicator_app(drill(&attacks), ui);
+            for (number, _, color, _) in indicators(&mut app).into_iter().take(attacks.len()) {
+                assert_eq!(color, strike_color(attacks[number - 1].1, palette));
+            }
+        }
+    }
+
+    #[test]
+    fn attacks_after_the_fourth_move_inside() {
+        let attacks = [
+            Opening::TopLeft,
+            Opening::TopRight,
+            Opening::BottomLeft,
+            Opening::BottomRight,
+        ]
+        .repeat(2)
+        .into_iter()
+        .map(|opening| (opening, Strike::Long))
+        .collect::<Vec<_>>();
+        let mut app = indicator_app(drill(&attacks), UiState::new());
+        let layout = CrossLayout::default();
+
+        for (number, position, _, _) in indicators(&mut app) {
+            let opening = attacks[number - 1].0;
+            let offset = position.truncate() - layout.center;
+            let expected_factor = if number > 4 { 0.5 } else { 1.0 };
+            assert_eq!(
+                offset,
+                opening_direction(opening) * layout.dist * expected_factor,
+                "attack {number}"
+            );
+        }
+    }
+
+    #[test]
+    fn streichen_are_counted_out() {
+        let mut meyer_cross = drill(&[(Opening::TopRight, Strike::Long); 4]);
+        meyer_cross.preparation = Some(Preparation::Streichen(StreichenCount::new(4).unwrap()));
+        let mut app = indicator_app(meyer_cross, UiState::new());
+        app.add_systems(Update, update_preparation_indicator);
+        let label = app
+            .world
+            .spawn((
+                Transform::default(),
+                Text::from_section("", TextStyle::default()),
+                Visibility::Visible,
+                PreparationIndicator,
+            ))
+            .id();
+        let text = |app: &App| {
+            app.world.get::<Text>(label).unwrap().sections[0]
+                .value
+                .clone()
+        };
+
+        app.update();
+        assert_eq!(text(&app), "Streichen 4x");
+
+        app.world.resource_mut::<Countdown>().start(8.0);
+        app.update();
+        assert_eq!(text(&app), "Streichen 1 of 4");
+
+        let timing = CueTiming::Fixed(1.0);
+        app.world
+            .resource_mut::<Countdown>()
+            .tick(Duration::from_secs_f32(5.0), 4, timing);
+        app.update();
+        assert_eq!(text(&app), "Streichen 3 of 4");
+    }
+}
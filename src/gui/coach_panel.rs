@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::resources::meyer_cross::MeyerCross;
+
+use super::{
+    attack_indicators::strike_color, pictograms::to_color32, response_indicators::defense_color,
+    setup_egui::UiState,
+};
+
+/// Shows the full drill to the coach while the fencer window may still hide parts of it.
+pub fn update_coach_panel(
+    ui_state: Res<UiState>,
+    meyer_cross: Res<MeyerCross>,
+    mut contexts: EguiContexts,
+) {
+    if !ui_state.fencer_window {
+        return;
+    }
+
+    let ctx = contexts.ctx_mut();
+    egui::Window::new("Sequence Preview").show(ctx, |ui| {
+        if let Some(preparation) = meyer_cross.preparation {
+            ui.label(format!("Preparation: {preparation}"));
+        }
+        egui::Grid::new("coach_preview").show(ui, |ui| {
+            for (index, attack) in meyer_cross.attack_sequence.iter().flatten().enumerate() {
+                let color = to_color32(strike_color(attack.strike, ui_state.palette));
+                ui.colored_label(color, ui_state.strike_glyph.label(index + 1, attack.strike));
+                ui.label(attack.opening.to_string());
+                ui.colored_label(color, attack.strike.to_string());
+                if let Some(response) = attack.response {
+                    ui.colored_label(to_color32(defense_color(response)), response.to_string());
+                }
+                ui.end_row();
+            }
+        });
+    });
+}
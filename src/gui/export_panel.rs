@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    export::{export_drills, pdf::CARDS_PER_PAGE},
+    resources::meyer_cross::MeyerCross,
+};
+
+use super::setup_egui::UiState;
+
+const EXPORTS: [(&str, &str); 3] = [
+    ("Save Card as SVG", "meyer_cross_card.svg"),
+    ("Save Card as PNG", "meyer_cross_card.png"),
+    ("Save PDF Sheet", "meyer_cross_sheet.pdf"),
+];
+
+/// Saves the current drill as card, the PDF sheet adds further drills generated with the
+/// current rules.
+pub fn update_export_panel(
+    ui_state: Res<UiState>,
+    meyer_cross: Res<MeyerCross>,
+    mut status: Local<String>,
+    mut contexts: EguiContexts,
+) {
+    let ctx = contexts.ctx_mut();
+    egui::Window::new("Export")
+        .default_open(false)
+        .show(ctx, |ui| {
+            for (label, file) in EXPORTS {
+                if ui.button(label).clicked() {
+                    let rules = ui_state.rules();
+                    let mut rng = rand::thread_rng();
+                    let drills = std::iter::once(meyer_cross.clone())
+                        .chain(
+                            (1..CARDS_PER_PAGE)
+                                .filter_map(|_| MeyerCross::random(&mut rng, &rules).ok()),
+                        )
+                        .collect::<Vec<_>>();
+                    *status = match export_drills(Path::new(file), &drills) {
+                        Ok(()) => format!("Saved {file}"),
+                        Err(e) => e.to_string(),
+                    };
+                }
+            }
+            ui.label(&*status);
+        });
+}
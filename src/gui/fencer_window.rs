@@ -0,0 +1,85 @@
+use bevy::{
+    prelude::*,
+    render::camera::RenderTarget,
+    window::{MonitorSelection, PrimaryWindow, WindowMode, WindowRef},
+};
+
+use super::setup_egui::UiState;
+
+/// Clean fullscreen view for the fencers, e.g. on a projector, while the primary window
+/// keeps the coach's controls.
+#[derive(Component)]
+pub struct FencerWindow;
+
+#[derive(Component)]
+pub struct FencerCamera;
+
+pub fn toggle_fencer_window(
+    mut commands: Commands,
+    mut ui: ResMut<UiState>,
+    windows: Query<Entity, With<FencerWindow>>,
+    cameras: Query<Entity, With<FencerCamera>>,
+    mut spawned: Local<bool>,
+) {
+    let window = windows.get_single().ok();
+    if *spawned && window.is_none() {
+        // Closed by the user, not by the settings.
+        ui.fencer_window = false;
+    }
+
+    match (ui.fencer_window, window) {
+        (true, None) => {
+            let window = commands
+                .spawn((
+                    Window {
+                        title: "Meyer Cross".into(),
+                        mode: WindowMode::BorderlessFullscreen,
+                        position: WindowPosition::Centered(MonitorSelection::Index(1)),
+                        ..default()
+                    },
+                    FencerWindow,
+                ))
+                .id();
+            commands.spawn((
+                Camera2dBundle {
+                    camera: Camera {
+                        target: RenderTarget::Window(WindowRef::Entity(window)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                FencerCamera,
+            ));
+            *spawned = true;
+        }
+        (false, window) => {
+            if let Some(window) = window {
+                commands.entity(window).despawn();
+            }
+            for camera in cameras.iter() {
+                commands.entity(camera).despawn();
+            }
+            *spawned = false;
+        }
+        (true, Some(_)) => (),
+    }
+}
+
+/// Zooms the coach's view out so the cross laid out for the fencer window stays visible.
+pub fn scale_coach_view(
+    fencer_windows: Query<&Window, With<FencerWindow>>,
+    primary_windows: Query<&Window, With<PrimaryWindow>>,
+    mut projections: Query<&mut OrthographicProjection, Without<FencerCamera>>,
+) {
+    let scale = match (fencer_windows.get_single(), primary_windows.get_single()) {
+        (Ok(fencer), Ok(coach)) if coach.width() > 0.0 && coach.height() > 0.0 => {
+            (fencer.width() / coach.width()).max(fencer.height() / coach.height())
+        }
+        _ => 1.0,
+    };
+    for mut projection in projections.iter_mut() {
+        if projection.scale != scale {
+            projection.scale = scale;
+        }
+    }
+}
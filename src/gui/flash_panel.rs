@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::resources::{
+    flash::{Flash, FlashPhase},
+    meyer_cross::MeyerCross,
+    session_log::{RatedAttack, SelfRating, SessionEntry, SessionLog},
+};
+
+use super::setup_egui::UiState;
+
+pub fn update_flash_panel(
+    meyer_cross: Res<MeyerCross>,
+    mut ui_state: ResMut<UiState>,
+    mut flash: ResMut<Flash>,
+    mut log: ResMut<SessionLog>,
+    mut contexts: EguiContexts,
+) {
+    if !flash.is_active() {
+        return;
+    }
+
+    let ctx = contexts.ctx_mut();
+    egui::Window::new("Flash Drill").show(ctx, |ui| {
+        match flash.phase() {
+            FlashPhase::Showing => {
+                ui.label(format!("Memorize: {:.1} s", flash.remaining_s()));
+            }
+            FlashPhase::Recall => {
+                ui.label("Execute from memory.");
+                if ui.button("Reveal").clicked() {
+                    flash.reveal();
+                }
+            }
+            FlashPhase::Review => {
+                let attacks = meyer_cross.attack_sequence.clone().unwrap_or_default();
+                egui::Grid::new("flash_ratings").show(ui, |ui| {
+                    for (index, attack) in attacks.iter().enumerate() {
+                        ui.label(format!(
+                            "{} {} {}",
+                            index + 1,
+                            attack.opening,
+                            attack.strike
+                        ));
+                        for rating in SelfRating::ALL {
+                            let selected = flash.ratings().get(index) == Some(&Some(rating));
+                            if ui
+                                .selectable_label(selected, format!("{rating:?}"))
+                                .clicked()
+                            {
+                                flash.rate(index, rating);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                if ui.button("Next Drill").clicked() {
+                    log.record(SessionEntry {
+                        preparation: meyer_cross.preparation,
+                        attacks: attacks
+                            .iter()
+                            .zip(flash.ratings())
+                            .map(|(&attack, &rating)| RatedAttack { attack, rating })
+                            .collect(),
+                    });
+                    ui_state.apply_pending = true;
+                }
+            }
+            FlashPhase::Inactive => (),
+        }
+
+        ui.separator();
+        ui.label(format!(
+            "Session: {} drills, {} good, {} unsure, {} wrong",
+            log.entries().len(),
+            log.rating_count(SelfRating::Good),
+            log.rating_count(SelfRating::Unsure),
+            log.rating_count(SelfRating::Wrong)
+        ));
+    });
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{meyer_cross::types::Opening, resources::meyer_cross::MeyerCross};
+
+use super::{
+    attack_indicators::{strike_color, strike_letter},
+    pictograms::to_color32,
+    setup_egui::UiState,
+};
+
+/// Initials of the opening, e.g. `TR` for the top right.
+pub fn opening_initials(opening: Opening) -> String {
+    opening
+        .to_string()
+        .split(' ')
+        .filter_map(|word| word.chars().next())
+        .collect()
+}
+
+/// Lists the recent drills, newest first, to see what was already practiced.
+pub fn update_history_panel(
+    ui_state: Res<UiState>,
+    meyer_cross: Res<MeyerCross>,
+    mut contexts: EguiContexts,
+) {
+    let ctx = contexts.ctx_mut();
+    egui::Window::new("History")
+        .default_open(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("drill_history").show(ui, |ui| {
+                for (index, drill) in meyer_cross.history().iter().enumerate() {
+                    ui.label(format!("-{}", index + 1));
+                    ui.label(drill.preparation.map_or(String::new(), |p| p.to_string()));
+                    ui.horizontal(|ui| {
+                        for attack in &drill.attack_sequence {
+                            ui.colored_label(
+                                to_color32(strike_color(attack.strike, ui_state.palette)),
+                                format!(
+                                    "{}{}",
+                                    opening_initials(attack.opening),
+                                    strike_letter(attack.strike)
+                                ),
+                            );
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        });
+}
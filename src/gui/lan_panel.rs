@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    lan::DEFAULT_PORT,
+    resources::{
+        countdown::Countdown,
+        lan_sync::{LanRole, LanSync},
+    },
+};
+
+pub struct LanPanelState {
+    port: u16,
+    status: String,
+}
+
+impl Default for LanPanelState {
+    fn default() -> Self {
+        LanPanelState {
+            port: DEFAULT_PORT,
+            status: String::new(),
+        }
+    }
+}
+
+/// Lets one station host the group drill and the others follow it.
+pub fn update_lan_panel(
+    mut lan: ResMut<LanSync>,
+    mut countdown: ResMut<Countdown>,
+    mut state: Local<LanPanelState>,
+    mut contexts: EguiContexts,
+) {
+    let ctx = contexts.ctx_mut();
+    egui::Window::new("Group Sync")
+        .default_open(false)
+        .show(ctx, |ui| {
+            ui.add(egui::DragValue::new(&mut state.port).prefix("Port: "));
+
+            let mut role = lan.role();
+            egui::ComboBox::from_label("Role")
+                .selected_text(role.to_string())
+                .show_ui(ui, |ui| {
+                    for value in LanRole::ALL {
+                        ui.selectable_value(&mut role, value, value.to_string());
+                    }
+                });
+
+            if role != lan.role() {
+                if lan.role() == LanRole::Client {
+                    countdown.stop();
+                }
+                state.status = match (lan.set_role(role, state.port), role) {
+                    (Err(e), _) => e.to_string(),
+                    (Ok(()), LanRole::Off) => String::new(),
+                    (Ok(()), LanRole::Host) => "Broadcasting drills to the network".to_string(),
+                    (Ok(()), LanRole::Client) => "Following the host".to_string(),
+                };
+            }
+            ui.label(&state.status);
+        });
+}
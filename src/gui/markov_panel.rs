@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::meyer_cross::{
+    markov::{MarkovGenerator, Weights},
+    types::{Opening, Strike},
+};
+
+use super::{
+    attack_indicators::strike_letter, history_panel::opening_initials, setup_egui::UiState,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub struct MarkovPanelState {
+    path: String,
+    status: String,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for MarkovPanelState {
+    fn default() -> Self {
+        MarkovPanelState {
+            path: "transition_matrix.txt".to_string(),
+            status: String::new(),
+        }
+    }
+}
+
+/// One row per previous opening or strike, one column per next one.
+fn weights_grid<T: Copy>(
+    ui: &mut egui::Ui,
+    id: &str,
+    values: &[T; 4],
+    label: impl Fn(T) -> String,
+    weights: &mut Weights,
+) {
+    egui::Grid::new(id).show(ui, |ui| {
+        ui.label("");
+        for value in values {
+            ui.label(label(*value));
+        }
+        ui.end_row();
+        for (value, row) in values.iter().zip(weights.iter_mut()) {
+            ui.label(label(*value));
+            for weight in row {
+                ui.add(
+                    egui::DragValue::new(weight)
+                        .speed(0.1)
+                        .clamp_range(0.0..=10.0),
+                );
+            }
+            ui.end_row();
+        }
+    });
+}
+
+/// Loads and saves the weights as a text file.
+#[cfg(not(target_arch = "wasm32"))]
+fn file_row(ui: &mut egui::Ui, ui_state: &mut UiState, state: &mut MarkovPanelState) {
+    use std::path::Path;
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.label("File:");
+        ui.text_edit_singleline(&mut state.path);
+    });
+    ui.horizontal(|ui| {
+        if ui.button("Load").clicked() {
+            state.status = match MarkovGenerator::load(Path::new(&state.path)) {
+                Ok(markov) => {
+                    ui_state.markov = markov;
+                    format!("Loaded {}", state.path)
+                }
+                Err(e) => e.to_string(),
+            };
+        }
+        if ui.button("Save").clicked() {
+            state.status = ui_state
+                .markov
+                .save(Path::new(&state.path))
+                .map_or_else(|e| e.to_string(), |()| format!("Saved {}", state.path));
+        }
+    });
+    ui.label(&state.status);
+}
+
+/// Edits the weights of the Markov chain generator, which apply with the other settings.
+pub fn update_markov_panel(
+    mut ui_state: ResMut<UiState>,
+    #[cfg(not(target_arch = "wasm32"))] mut state: Local<MarkovPanelState>,
+    mut contexts: EguiContexts,
+) {
+    let ctx = contexts.ctx_mut();
+    egui::Window::new("Transition Matrix")
+        .default_open(false)
+        .show(ctx, |ui| {
+            ui.label("From the row's to the column's opening:");
+            weights_grid(
+                ui,
+                "opening_weights",
+                &Opening::ALL,
+                opening_initials,
+                &mut ui_state.markov.openings,
+            );
+            ui.label("From the row's to the column's strike:");
+            weights_grid(
+                ui,
+                "strike_weights",
+                &Strike::ALL,
+                |strike| strike_letter(strike).to_string(),
+                &mut ui_state.markov.strikes,
+            );
+            if ui.button("Reset").clicked() {
+                ui_state.markov = MarkovGenerator::default();
+            }
+
+            // Browsers can neither read nor write files.
+            #[cfg(not(target_arch = "wasm32"))]
+            file_row(ui, &mut ui_state, &mut state);
+        });
+}
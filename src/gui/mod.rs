@@ -1 +1,19 @@
+pub mod attack_indicators;
+pub mod coach_panel;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod export_panel;
+pub mod fencer_window;
+pub mod flash_panel;
+pub mod history_panel;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod lan_panel;
+pub mod markov_panel;
+pub mod pictograms;
+pub mod preview_strip;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod profile_panel;
+pub mod quiz_panel;
+pub mod reaction_panel;
+pub mod response_indicators;
 pub mod setup_egui;
+pub mod share_panel;
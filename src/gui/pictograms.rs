@@ -0,0 +1,66 @@
+use bevy::prelude::Color;
+use bevy_egui::egui::{self, Color32, Painter, Pos2, Rect, Stroke};
+
+use crate::meyer_cross::types::{Guard, Opening};
+
+pub fn to_color32(color: Color) -> Color32 {
+    let [r, g, b, a] = color.as_rgba_u8();
+    Color32::from_rgba_unmultiplied(r, g, b, a)
+}
+
+/// Maps normalized coordinates with the origin at the bottom left into `rect`.
+fn at(rect: Rect, x: f32, y: f32) -> Pos2 {
+    egui::pos2(
+        rect.left() + x * rect.width(),
+        rect.bottom() - y * rect.height(),
+    )
+}
+
+/// Schematic fencer seen from behind, so left and right match the fencer's own view.
+pub fn guard_pictogram(painter: &Painter, rect: Rect, guard: Guard, color: Color32) {
+    let stroke = Stroke::new(3.0, color);
+    let line = |from: (f32, f32), to: (f32, f32)| {
+        painter.line_segment([at(rect, from.0, from.1), at(rect, to.0, to.1)], stroke);
+    };
+
+    painter.circle_stroke(at(rect, 0.5, 0.62), 0.05 * rect.height(), stroke);
+    line((0.4, 0.55), (0.6, 0.55));
+    line((0.5, 0.57), (0.5, 0.3));
+    line((0.5, 0.3), (0.4, 0.02));
+    line((0.5, 0.3), (0.6, 0.02));
+
+    let (hands, tip) = match guard {
+        Guard::TagAbove => ((0.5, 0.75), (0.5, 0.98)),
+        Guard::TagLeft => ((0.38, 0.6), (0.25, 0.9)),
+        Guard::TagRight => ((0.62, 0.6), (0.75, 0.9)),
+        Guard::PflugLeft => ((0.42, 0.32), (0.47, 0.48)),
+        Guard::PflugRight => ((0.58, 0.32), (0.53, 0.48)),
+        Guard::OchsLeft => ((0.33, 0.62), (0.45, 0.55)),
+        Guard::OchsRight => ((0.67, 0.62), (0.55, 0.55)),
+        Guard::Eisenport => ((0.5, 0.25), (0.5, 0.4)),
+        Guard::Langort => ((0.5, 0.45), (0.5, 0.47)),
+        Guard::Alber => ((0.5, 0.3), (0.5, 0.08)),
+    };
+    line((0.4, 0.55), hands);
+    line((0.6, 0.55), hands);
+    painter.line_segment(
+        [at(rect, hands.0, hands.1), at(rect, tip.0, tip.1)],
+        Stroke::new(5.0, Color32::GRAY),
+    );
+    painter.circle_filled(at(rect, tip.0, tip.1), 4.0, Color32::GRAY);
+}
+
+/// The cross with the given opening marked.
+pub fn opening_pictogram(painter: &Painter, rect: Rect, opening: Opening, color: Color32) {
+    let stroke = Stroke::new(3.0, Color32::GRAY);
+    painter.line_segment([rect.left_top(), rect.right_bottom()], stroke);
+    painter.line_segment([rect.right_top(), rect.left_bottom()], stroke);
+
+    let (x, y) = match opening {
+        Opening::TopLeft => (0.2, 0.8),
+        Opening::TopRight => (0.8, 0.8),
+        Opening::BottomLeft => (0.2, 0.2),
+        Opening::BottomRight => (0.8, 0.2),
+    };
+    painter.circle_filled(at(rect, x, y), 0.08 * rect.height(), color);
+}
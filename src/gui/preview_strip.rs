@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+
+use crate::{
+    meyer_cross::{mirror::Mirror, types::Attack},
+    resources::{
+        countdown::Countdown,
+        flash::Flash,
+        meyer_cross::{MeyerCross, QueuedDrill},
+    },
+};
+
+use super::{
+    attack_indicators::{opening_direction, strike_color, CrossLayout},
+    setup_egui::UiState,
+};
+
+const CURRENT_ROW: usize = 0;
+const NEXT_ROW: usize = 1;
+
+/// Strike glyph of one cell of the sequence strip at the bottom of the screen.
+#[derive(Component)]
+pub struct StripLabel {
+    row: usize,
+    index: usize,
+}
+
+pub fn spawn_strip_labels(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("OpenSans-Regular.ttf");
+    for row in [CURRENT_ROW, NEXT_ROW] {
+        for index in 0..8 {
+            commands.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: font.clone(),
+                            ..default()
+                        },
+                    )
+                    .with_alignment(TextAlignment::Center),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                StripLabel { row, index },
+            ));
+        }
+    }
+}
+
+fn strip_rows<'a>(
+    ui: &UiState,
+    flash: &Flash,
+    meyer_cross: &'a MeyerCross,
+    queued: &'a QueuedDrill,
+) -> [Option<&'a Vec<Attack>>; 2] {
+    if !ui.preview_strip || flash.is_hidden() {
+        return [None, None];
+    }
+    [
+        meyer_cross.attack_sequence.as_ref(),
+        queued
+            .drill
+            .as_ref()
+            .and_then(|d| d.attack_sequence.as_ref())
+            .filter(|_| ui.queue_next_drill),
+    ]
+}
+
+/// The queued drill is dimmed so it is not mistaken for the current one.
+fn row_color(row: usize, color: Color) -> Color {
+    if row == NEXT_ROW {
+        color.with_a(0.5)
+    } else {
+        color
+    }
+}
+
+pub fn update_strip_labels(
+    ui: Res<UiState>,
+    flash: Res<Flash>,
+    layout: Res<CrossLayout>,
+    meyer_cross: Res<MeyerCross>,
+    queued: Res<QueuedDrill>,
+    mut query: Query<(&mut Transform, &mut Text, &mut Visibility, &StripLabel)>,
+) {
+    let rows = strip_rows(&ui, &flash, &meyer_cross, &queued);
+    for (mut transform, mut text, mut visibility, label) in query.iter_mut() {
+        let attacks = rows[label.row];
+        let Some(attack) = attacks.and_then(|a| a.get(label.index)) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Visible;
+
+        let cell = layout.strip_cell_position(label.row, label.index, attacks.map_or(0, Vec::len));
+        let cell_width = layout.strip_cell * CrossLayout::STRIP_CELL_ASPECT;
+        transform.translation = (cell + Vec2::new(0.2 * cell_width, 0.0)).extend(0.0);
+
+        let section = &mut text.sections[0];
+        section.value = ui.strike_glyph.label(label.index + 1, attack.strike);
+        section.style.font_size = 0.5 * layout.strip_cell;
+        section.style.color = row_color(label.row, strike_color(attack.strike, ui.palette));
+    }
+}
+
+pub fn draw_strip_arrows(
+    ui: Res<UiState>,
+    flash: Res<Flash>,
+    countdown: Res<Countdown>,
+    layout: Res<CrossLayout>,
+    meyer_cross: Res<MeyerCross>,
+    queued: Res<QueuedDrill>,
+    mut gizmos: Gizmos,
+) {
+    let rows = strip_rows(&ui, &flash, &meyer_cross, &queued);
+    let cell_width = layout.strip_cell * CrossLayout::STRIP_CELL_ASPECT;
+    let arrow_length = 0.3 * layout.strip_cell;
+
+    for (row, attacks) in rows.iter().enumerate() {
+        let Some(attacks) = attacks else {
+            continue;
+        };
+        for (index, attack) in attacks.iter().enumerate() {
+            let cell = layout.strip_cell_position(row, index, attacks.len());
+            let color = row_color(row, strike_color(attack.strike, ui.palette));
+            let direction =
+                opening_direction(attack.opening.mirrored_if(ui.partner_perspective)).normalize();
+            let arrow_center = cell - Vec2::new(0.25 * cell_width, 0.0);
+            let tip = arrow_center + direction * arrow_length;
+            gizmos.line_2d(arrow_center - direction * arrow_length, tip, color);
+            for side in [-1.0, 1.0] {
+                let head = Vec2::from_angle(side * 2.6).rotate(direction);
+                gizmos.line_2d(tip, tip + head * 0.5 * arrow_length, color);
+            }
+
+            let is_current = row == CURRENT_ROW
+                && countdown.is_active()
+                && countdown.revealed().checked_sub(1) == Some(index);
+            if is_current {
+                gizmos.rect_2d(
+                    cell,
+                    0.0,
+                    Vec2::new(cell_width, layout.strip_cell) * 0.95,
+                    Color::YELLOW,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::resources::profile::TrainingProfile;
+
+use super::{
+    attack_indicators::{strike_color, strike_letter},
+    history_panel::opening_initials,
+    pictograms::to_color32,
+    setup_egui::UiState,
+};
+
+pub struct ProfilePanelState {
+    name: String,
+    status: String,
+}
+
+impl Default for ProfilePanelState {
+    fn default() -> Self {
+        ProfilePanelState {
+            name: TrainingProfile::DEFAULT_NAME.to_string(),
+            status: String::new(),
+        }
+    }
+}
+
+/// Switches between the fencers' profiles and lists the transitions due for repetition.
+pub fn update_profile_panel(
+    ui_state: Res<UiState>,
+    mut profile: ResMut<TrainingProfile>,
+    mut state: Local<ProfilePanelState>,
+    mut contexts: EguiContexts,
+) {
+    let ctx = contexts.ctx_mut();
+    egui::Window::new("Profile")
+        .default_open(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.text_edit_singleline(&mut state.name);
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Load").clicked() {
+                    state.status = match TrainingProfile::load(&state.name) {
+                        Ok(loaded) => {
+                            *profile = loaded;
+                            format!("Training as {}", profile.name)
+                        }
+                        Err(e) => e.to_string(),
+                    };
+                }
+                if ui.button("Save").clicked() {
+                    state.status = match TrainingProfile::new(&state.name) {
+                        Ok(renamed) => {
+                            profile.name = renamed.name;
+                            profile
+                                .save()
+                                .map_or_else(|e| e.to_string(), |()| "Saved".to_string())
+                        }
+                        Err(e) => e.to_string(),
+                    };
+                }
+            });
+            ui.label(&state.status);
+            ui.separator();
+
+            let due = profile.schedule.due();
+            ui.label(format!(
+                "Drill {}, {} transitions due",
+                profile.schedule.clock(),
+                due.len()
+            ));
+            egui::Grid::new("due_transitions").show(ui, |ui| {
+                for (transition, card) in due {
+                    ui.colored_label(
+                        to_color32(strike_color(transition.strike, ui_state.palette)),
+                        format!(
+                            "{} → {}{}",
+                            opening_initials(transition.from),
+                            opening_initials(transition.to),
+                            strike_letter(transition.strike)
+                        ),
+                    );
+                    ui.label(format!("Level {}", card.level));
+                    ui.end_row();
+                }
+            });
+        });
+}
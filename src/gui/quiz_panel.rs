@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::resources::quiz::{Quiz, QuizItem};
+
+use super::{
+    attack_indicators::strike_color,
+    pictograms::{guard_pictogram, opening_pictogram, to_color32},
+    setup_egui::UiState,
+};
+
+fn pictogram(ui: &mut egui::Ui, ui_state: &UiState, item: QuizItem) {
+    let (response, painter) = ui.allocate_painter(egui::vec2(200.0, 200.0), egui::Sense::hover());
+    let rect = response.rect.shrink(10.0);
+    match item {
+        QuizItem::Guard(guard) => guard_pictogram(&painter, rect, guard, egui::Color32::WHITE),
+        QuizItem::Opening(opening) => {
+            opening_pictogram(&painter, rect, opening, egui::Color32::WHITE)
+        }
+        QuizItem::Strike(strike) => {
+            let color = to_color32(strike_color(strike, ui_state.palette));
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                ui_state.strike_glyph.label(1, strike),
+                egui::FontId::proportional(0.5 * rect.height()),
+                color,
+            );
+        }
+    }
+}
+
+pub fn update_quiz_panel(
+    ui_state: Res<UiState>,
+    mut quiz: ResMut<Quiz>,
+    mut contexts: EguiContexts,
+) {
+    if !ui_state.quiz_mode {
+        return;
+    }
+
+    let ctx = contexts.ctx_mut();
+    egui::Window::new("Terminology Quiz").show(ctx, |ui| {
+        let question = quiz.question().clone();
+        ui.label(match question.item {
+            QuizItem::Guard(_) => "Which guard is this?",
+            QuizItem::Opening(_) => "Which opening is marked?",
+            QuizItem::Strike(_) => "Which strike is marked like this?",
+        });
+        pictogram(ui, &ui_state, question.item);
+
+        ui.horizontal(|ui| {
+            for choice in question.choices {
+                if ui.button(choice.to_string()).clicked() {
+                    quiz.answer(choice);
+                }
+            }
+        });
+
+        if let Some((item, correct)) = quiz.last_answer() {
+            ui.label(if correct {
+                "Correct!".to_string()
+            } else {
+                format!("Wrong, that was {item}.")
+            });
+        }
+
+        ui.separator();
+        egui::Grid::new("quiz_accuracy").show(ui, |ui| {
+            for (item, accuracy) in quiz.accuracy() {
+                ui.label(item.to_string());
+                ui.label(format!("{} / {}", accuracy.correct, accuracy.asked));
+                ui.end_row();
+            }
+        });
+    });
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::resources::reaction_log::{ReactionAverage, ReactionLog};
+
+use super::setup_egui::UiState;
+
+fn average_row(ui: &mut egui::Ui, label: impl Into<egui::WidgetText>, average: ReactionAverage) {
+    ui.label(label);
+    ui.label(format!("{:.3} s", average.average_s));
+    ui.label(format!("n = {}", average.count));
+    ui.end_row();
+}
+
+fn histogram(ui: &mut egui::Ui, log: &ReactionLog) {
+    let bins = log.histogram();
+    let max = bins.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let (response, painter) = ui.allocate_painter(egui::vec2(300.0, 100.0), egui::Sense::hover());
+    let rect = response.rect;
+    let bin_width = rect.width() / bins.len() as f32;
+    for (i, &count) in bins.iter().enumerate() {
+        let height = rect.height() * count as f32 / max;
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(rect.left() + i as f32 * bin_width, rect.bottom() - height),
+            egui::pos2(
+                rect.left() + (i + 1) as f32 * bin_width - 1.0,
+                rect.bottom(),
+            ),
+        );
+        painter.rect_filled(bar, 0.0, egui::Color32::LIGHT_BLUE);
+    }
+    ui.label(format!(
+        "0 to {:.1} s in {:.1} s steps, last bar includes slower reactions",
+        ReactionLog::HISTOGRAM_BIN_S * ReactionLog::HISTOGRAM_BINS as f32,
+        ReactionLog::HISTOGRAM_BIN_S
+    ));
+}
+
+pub fn update_reaction_panel(
+    ui_state: Res<UiState>,
+    mut log: ResMut<ReactionLog>,
+    mut contexts: EguiContexts,
+) {
+    let session_over = ui_state.reaction_measurement && !ui_state.timer_active;
+    let Some(average) = log.average().filter(|_| session_over) else {
+        return;
+    };
+
+    let ctx = contexts.ctx_mut();
+    egui::Window::new("Reaction Times").show(ctx, |ui| {
+        egui::Grid::new("reaction_averages").show(ui, |ui| {
+            average_row(ui, "All attacks", average);
+            ui.label("Missed cues");
+            ui.label(log.missed().to_string());
+            ui.end_row();
+
+            ui.label("Per Attack:");
+            ui.end_row();
+            for (index, average) in log.average_by_index() {
+                average_row(ui, format!("Attack {}", index + 1), average);
+            }
+
+            ui.label("Per Strike:");
+            ui.end_row();
+            for (strike, average) in log.average_by_strike() {
+                average_row(ui, strike.to_string(), average);
+            }
+
+            ui.label("Per Opening:");
+            ui.end_row();
+            for (opening, average) in log.average_by_opening() {
+                average_row(ui, opening.to_string(), average);
+            }
+        });
+
+        histogram(ui, &log);
+
+        if ui.button("Clear").clicked() {
+            log.clear();
+        }
+    });
+}
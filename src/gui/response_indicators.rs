@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+
+use crate::{
+    meyer_cross::{defense::Defense, mirror::Mirror},
+    resources::{countdown::Countdown, flash::Flash, meyer_cross::MeyerCross},
+};
+
+use super::{attack_indicators::CrossLayout, setup_egui::UiState};
+
+/// The partner's expected response to the attack with this number, on the partner's cross.
+#[derive(Component)]
+pub struct ResponseIndicator(pub usize);
+
+pub fn defense_color(defense: Defense) -> Color {
+    match defense {
+        Defense::Parry => Color::CYAN,
+        Defense::Versetzen => Color::ORANGE,
+        Defense::CounterCut => Color::LIME_GREEN,
+    }
+}
+
+pub fn spawn_response_indicators(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("OpenSans-Regular.ttf");
+    for id in 1..=8 {
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    "",
+                    TextStyle {
+                        font: font.clone(),
+                        ..default()
+                    },
+                )
+                .with_alignment(TextAlignment::Center),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            ResponseIndicator(id),
+        ));
+    }
+}
+
+/// Shows each revealed response where the partner meets the attack, seen from the partner's
+/// side of the cross: the attacker's view mirrored, at the real target of a Fehler.
+pub fn position_response_indicators(
+    meyer_cross: Res<MeyerCross>,
+    countdown: Res<Countdown>,
+    flash: Res<Flash>,
+    ui: Res<UiState>,
+    layout: Res<CrossLayout>,
+    mut query: Query<(
+        &mut Transform,
+        &mut Text,
+        &mut Visibility,
+        &ResponseIndicator,
+    )>,
+) {
+    let attacks = meyer_cross.attack_sequence.as_deref().unwrap_or_default();
+    for (mut transform, mut text, mut visibility, indicator) in query.iter_mut() {
+        let index = indicator.0 - 1;
+        let shown = attacks
+            .get(index)
+            .filter(|_| countdown.is_revealed(index) && !flash.is_hidden())
+            .and_then(|attack| {
+                let opening = attack.target.unwrap_or(attack.opening);
+                let position = layout
+                    .partner_position(indicator.0, opening.mirrored_if(!ui.partner_perspective))?;
+                Some((position, attack.response?))
+            });
+        let Some((position, defense)) = shown else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Visible;
+        transform.translation = position;
+        let section = &mut text.sections[0];
+        section.value = format!("{}{}", indicator.0, defense.letter());
+        section.style.color = defense_color(defense);
+        section.style.font_size = layout.font_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meyer_cross::types::{Attack, Opening, Strike};
+
+    #[test]
+    fn responses_face_the_attacker_on_the_partner_cross() {
+        let mut meyer_cross = MeyerCross::new();
+        meyer_cross.attack_sequence = Some(vec![
+            Attack {
+                response: Some(Defense::Parry),
+                ..Attack::new(Opening::TopLeft, Strike::Long)
+            };
+            4
+        ]);
+        let layout = CrossLayout::split(1280.0, 720.0, 0);
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(meyer_cross)
+            .insert_resource(Countdown::default())
+            .insert_resource(Flash::default())
+            .insert_resource(UiState::new())
+            .insert_resource(layout)
+            .add_systems(Update, position_response_indicators);
+        let indicator = app
+            .world
+            .spawn((
+                Transform::default(),
+                Text::from_section("", TextStyle::default()),
+                Visibility::Hidden,
+                ResponseIndicator(1),
+            ))
+            .id();
+        app.update();
+
+        let partner = layout.partner_center.unwrap();
+        assert!(partner.x > layout.center.x);
+        assert_eq!(
+            app.world.get::<Transform>(indicator).unwrap().translation,
+            layout.partner_position(1, Opening::TopRight).unwrap()
+        );
+        assert_eq!(
+            app.world.get::<Text>(indicator).unwrap().sections[0].value,
+            "1P"
+        );
+        assert_eq!(
+            app.world.get::<Visibility>(indicator),
+            Some(&Visibility::Visible)
+        );
+    }
+}
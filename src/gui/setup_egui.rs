@@ -1,24 +1,94 @@
 use bevy_egui::{egui, EguiContexts, EguiPlugin};
 
-use crate::meyer_cross::attack_sequence::AttackSequenceLength;
+use std::collections::HashSet;
+
+use crate::{
+    meyer_cross::{
+        attack_sequence::AttackSequenceLength,
+        defense::Defense,
+        difficulty::DifficultyScore,
+        fehler::FehlerTarget,
+        history::{DrillHistory, RepeatAvoidance},
+        markov::MarkovGenerator,
+        mirror::Mirror,
+        preparation::{
+            Preparation,
+            Preparation::{Static, Streichen},
+            StreichenCount,
+        },
+        rules::DrillRules,
+        types::{Guard, Strike},
+    },
+    resources::{
+        countdown::{Countdown, CueTiming},
+        meyer_cross::{MeyerCross, SequenceWalk},
+        progression::Progression,
+    },
+};
 
 use bevy::{app::Plugin, prelude::*};
 
+use crate::resources::quiz::Quiz;
+
+use super::{
+    attack_indicators::{Palette, StrikeGlyph},
+    coach_panel::update_coach_panel,
+    fencer_window::{scale_coach_view, toggle_fencer_window},
+    flash_panel::update_flash_panel,
+    history_panel::update_history_panel,
+    markov_panel::update_markov_panel,
+    quiz_panel::update_quiz_panel,
+    reaction_panel::update_reaction_panel,
+    share_panel::update_share_panel,
+};
+
 pub struct MeyerUiPlugin;
 
 impl Plugin for MeyerUiPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(EguiPlugin)
             .insert_resource(UiState::new())
-            .add_systems(Update, update_ui);
+            .insert_resource(Quiz::default())
+            .add_systems(
+                Update,
+                (
+                    update_ui,
+                    update_reaction_panel,
+                    update_flash_panel,
+                    update_quiz_panel,
+                    update_coach_panel,
+                    toggle_fencer_window,
+                    scale_coach_view,
+                    update_share_panel,
+                    update_history_panel,
+                    update_markov_panel,
+                ),
+            );
+
+        // Drills shared as link arrive in the page address.
+        #[cfg(target_arch = "wasm32")]
+        app.add_systems(Startup, super::share_panel::load_shared_drill);
+
+        // Browsers can neither read or write files nor open UDP sockets.
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(
+            Update,
+            (
+                super::export_panel::update_export_panel,
+                super::lan_panel::update_lan_panel,
+                super::profile_panel::update_profile_panel,
+            ),
+        );
     }
 }
 
 #[derive(Resource)]
 pub struct UiState {
     pub streichen: bool,
-    pub random_streichen: bool,
+    /// Whether each count from [`StreichenCount::MIN`] on is allowed.
+    pub streichen_counts: [bool; StreichenCount::MAX],
     pub huten: bool,
+    pub guard_start: bool,
     pub tag_left: bool,
     pub tag_right: bool,
     pub tag_above: bool,
@@ -34,20 +104,52 @@ pub struct UiState {
     pub flat_allowed: bool,
     pub fehler_allowed: bool,
     pub doppelfehler_allowed: bool,
+    pub fehler_target: FehlerTarget,
     pub attack_count_min: usize,
     pub attack_count_max: usize,
     pub timer_active: bool,
     pub time_for_preparation_s: usize,
     pub time_per_strike_s: usize,
+    pub random_cue_delay: bool,
+    pub cue_delay_min_s: f32,
+    pub cue_delay_max_s: f32,
+    pub reaction_measurement: bool,
+    pub flash_mode: bool,
+    pub flash_show_s: usize,
+    pub quiz_mode: bool,
+    pub left_handed: bool,
+    pub partner_perspective: bool,
+    /// Drills a partner's response to every attack on a split screen.
+    pub partner_drill: bool,
+    pub parry_allowed: bool,
+    pub versetzen_allowed: bool,
+    pub counter_cut_allowed: bool,
+    pub palette: Palette,
+    pub strike_glyph: StrikeGlyph,
+    pub fencer_window: bool,
+    pub preview_strip: bool,
+    pub queue_next_drill: bool,
+    pub walk_all_sequences: bool,
+    pub markov_chain: bool,
+    pub markov: MarkovGenerator,
+    pub repeat_avoidance: RepeatAvoidance,
+    pub repeat_window: usize,
+    pub adaptive_difficulty: bool,
+    pub spaced_repetition: bool,
+    pub reaction_target_s: f32,
     pub apply_pending: bool,
+    pub rules_changed: bool,
 }
 
 impl UiState {
-    fn new() -> UiState {
+    pub fn new() -> UiState {
         UiState {
             streichen: true,
-            random_streichen: false,
+            streichen_counts: std::array::from_fn(|i| {
+                i + StreichenCount::MIN == usize::from(StreichenCount::default())
+            }),
             huten: false,
+            guard_start: false,
             tag_left: false,
             tag_right: false,
             tag_above: false,
@@ -63,16 +165,181 @@ impl UiState {
             flat_allowed: false,
             fehler_allowed: false,
             doppelfehler_allowed: false,
+            fehler_target: FehlerTarget::HighLow,
             attack_count_min: 4,
             attack_count_max: 4,
             timer_active: false,
             time_for_preparation_s: 6,
             time_per_strike_s: 2,
+            random_cue_delay: false,
+            cue_delay_min_s: 0.4,
+            cue_delay_max_s: 2.0,
+            reaction_measurement: false,
+            flash_mode: false,
+            flash_show_s: 5,
+            quiz_mode: false,
+            left_handed: false,
+            partner_perspective: false,
+            partner_drill: false,
+            parry_allowed: true,
+            versetzen_allowed: true,
+            counter_cut_allowed: true,
+            palette: Palette::Classic,
+            strike_glyph: StrikeGlyph::None,
+            fencer_window: false,
+            preview_strip: false,
+            queue_next_drill: false,
+            walk_all_sequences: false,
+            markov_chain: false,
+            markov: MarkovGenerator::default(),
+            repeat_avoidance: RepeatAvoidance::Off,
+            repeat_window: 3,
+            adaptive_difficulty: false,
+            spaced_repetition: false,
+            reaction_target_s: 0.8,
             apply_pending: true,
+            rules_changed: true,
+        }
+    }
+
+    pub fn rules(&self) -> DrillRules {
+        let mut preps = HashSet::new();
+        fn insert_prep(preps: &mut HashSet<Preparation>, prep: Preparation, cond: bool) {
+            if cond {
+                preps.insert(prep);
+            }
+        }
+
+        if self.streichen {
+            preps.extend(
+                StreichenCount::all()
+                    .zip(self.streichen_counts)
+                    .filter(|(_, allowed)| *allowed)
+                    .map(|(count, _)| Streichen(count)),
+            );
+            if preps.is_empty() {
+                preps.insert(Streichen(StreichenCount::default()));
+            }
+        }
+
+        insert_prep(&mut preps, Static(Guard::TagLeft), self.tag_left);
+        insert_prep(&mut preps, Static(Guard::TagRight), self.tag_right);
+        insert_prep(&mut preps, Static(Guard::TagAbove), self.tag_above);
+        insert_prep(&mut preps, Static(Guard::PflugLeft), self.pflug_left);
+        insert_prep(&mut preps, Static(Guard::PflugRight), self.pflug_right);
+        insert_prep(&mut preps, Static(Guard::OchsLeft), self.ochs_left);
+        insert_prep(&mut preps, Static(Guard::OchsRight), self.ochs_right);
+        insert_prep(&mut preps, Static(Guard::Eisenport), self.eisenport);
+        insert_prep(&mut preps, Static(Guard::Langort), self.langort);
+        insert_prep(&mut preps, Static(Guard::Alber), self.alber);
+
+        let mut strikes = HashSet::new();
+        fn insert_strike(strikes: &mut HashSet<Strike>, strike: Strike, condition: bool) {
+            if condition {
+                strikes.insert(strike);
+            }
+        }
+
+        insert_strike(&mut strikes, Strike::Long, self.long_edge_allowed);
+        insert_strike(&mut strikes, Strike::Short, self.short_edge_allowed);
+        insert_strike(&mut strikes, Strike::Flat, self.flat_allowed);
+        insert_strike(&mut strikes, Strike::Fehler, self.fehler_allowed);
+
+        let defenses = [
+            (Defense::Parry, self.parry_allowed),
+            (Defense::Versetzen, self.versetzen_allowed),
+            (Defense::CounterCut, self.counter_cut_allowed),
+        ]
+        .into_iter()
+        .filter(|&(_, allowed)| self.partner_drill && allowed)
+        .map(|(defense, _)| defense)
+        .collect();
+
+        DrillRules {
+            preparations: preps,
+            strikes,
+            min_length: self.attack_count_min,
+            max_length: self.attack_count_max,
+            doppelfehler_enabled: self.doppelfehler_allowed,
+            left_handed: self.left_handed,
+            guard_start: self.guard_start,
+            fehler_target: self.fehler_target,
+            repeat_avoidance: self.repeat_avoidance,
+            repeat_window: self.repeat_window,
+            markov: self.markov_chain.then(|| self.markov.clone()),
+            defenses,
+        }
+    }
+
+    /// Sets the checkboxes from the rules, the inverse of [`UiState::rules`].
+    pub fn apply_rules(&mut self, rules: &DrillRules) {
+        let has = |prep| rules.preparations.contains(&prep);
+        self.streichen = StreichenCount::all().any(|count| has(Streichen(count)));
+        if self.streichen {
+            for (count, allowed) in StreichenCount::all().zip(self.streichen_counts.iter_mut()) {
+                *allowed = has(Streichen(count));
+            }
+        }
+        self.tag_left = has(Static(Guard::TagLeft));
+        self.tag_right = has(Static(Guard::TagRight));
+        self.tag_above = has(Static(Guard::TagAbove));
+        self.pflug_left = has(Static(Guard::PflugLeft));
+        self.pflug_right = has(Static(Guard::PflugRight));
+        self.ochs_left = has(Static(Guard::OchsLeft));
+        self.ochs_right = has(Static(Guard::OchsRight));
+        self.eisenport = has(Static(Guard::Eisenport));
+        self.langort = has(Static(Guard::Langort));
+        self.alber = has(Static(Guard::Alber));
+        self.huten = rules
+            .preparations
+            .iter()
+            .any(|prep| matches!(prep, Static(_)));
+
+        self.long_edge_allowed = rules.strikes.contains(&Strike::Long);
+        self.short_edge_allowed = rules.strikes.contains(&Strike::Short);
+        self.flat_allowed = rules.strikes.contains(&Strike::Flat);
+        self.fehler_allowed = rules.strikes.contains(&Strike::Fehler);
+        self.doppelfehler_allowed = rules.doppelfehler_enabled;
+        self.attack_count_min = rules.min_length;
+        self.attack_count_max = rules.max_length;
+        self.left_handed = rules.left_handed;
+        self.guard_start = rules.guard_start;
+        self.fehler_target = rules.fehler_target;
+        self.partner_drill = !rules.defenses.is_empty();
+        if self.partner_drill {
+            self.parry_allowed = rules.defenses.contains(&Defense::Parry);
+            self.versetzen_allowed = rules.defenses.contains(&Defense::Versetzen);
+            self.counter_cut_allowed = rules.defenses.contains(&Defense::CounterCut);
+        }
+        self.rules_changed = true;
+    }
+
+    pub fn cue_timing(&self) -> CueTiming {
+        if self.random_cue_delay {
+            CueTiming::Random {
+                min_s: self.cue_delay_min_s,
+                max_s: self.cue_delay_max_s,
+            }
+        } else {
+            CueTiming::Fixed(self.time_per_strike_s as f32)
         }
     }
 }
 
+macro_rules! setting_combo_box {
+    ($ui:expr, $ui_state:expr, $text:expr, $property:ident, $values:expr) => {
+        $ui.label($text);
+        egui::ComboBox::from_id_source(stringify!($property))
+            .selected_text($ui_state.$property.to_string())
+            .show_ui($ui, |ui| {
+                for value in $values {
+                    ui.selectable_value(&mut $ui_state.$property, value, value.to_string());
+                }
+            });
+        $ui.end_row();
+    };
+}
+
 macro_rules! setting_checkbox {
     ($ui:expr, $ui_state:expr, $text:expr, $property:ident) => {
         $ui.label($text);
@@ -81,26 +348,47 @@ macro_rules! setting_checkbox {
     };
 }
 
-fn update_ui(mut ui_state: ResMut<UiState>, mut contexts: EguiContexts) {
+fn update_ui(
+    mut ui_state: ResMut<UiState>,
+    mut countdown: ResMut<Countdown>,
+    walk: Res<SequenceWalk>,
+    meyer_cross: Res<MeyerCross>,
+    progression: Res<Progression>,
+    mut contexts: EguiContexts,
+) {
     let ctx = contexts.ctx_mut();
     egui::Window::new("Meyer Cross Settings").show(ctx, |ui| {
         egui::Grid::new("preview").show(ui, |ui| {
             setting_checkbox!(ui, ui_state, "Streichen Preparation", streichen);
             if ui_state.streichen {
-                setting_checkbox!(ui, ui_state, "Random Streichen Count", random_streichen);
+                ui.label("Streichen Counts");
+                ui.horizontal(|ui| {
+                    for (count, allowed) in
+                        StreichenCount::all().zip(ui_state.streichen_counts.iter_mut())
+                    {
+                        ui.checkbox(allowed, usize::from(count).to_string());
+                    }
+                });
+                ui.end_row();
             }
 
             setting_checkbox!(ui, ui_state, "Huten Preperation", huten);
             if ui_state.huten {
-                setting_checkbox!(ui, ui_state, "Tag Left", tag_left);
-                setting_checkbox!(ui, ui_state, "Tag Right", tag_right);
-                setting_checkbox!(ui, ui_state, "Tag Above", tag_above);
-                setting_checkbox!(ui, ui_state, "Pflug Left", pflug_left);
-                setting_checkbox!(ui, ui_state, "Pflug Right", pflug_right);
-                setting_checkbox!(ui, ui_state, "Ochs Left", ochs_left);
-                setting_checkbox!(ui, ui_state, "Ochs Right", ochs_right);
-                setting_checkbox!(ui, ui_state, "Eisenport", eisenport);
-                setting_checkbox!(ui, ui_state, "Alber", alber);
+                // Left-handed drills are mirrored after generation, so label each guard
+                // with the name the fencer will actually see.
+                let left_handed = ui_state.left_handed;
+                let guard_label = |guard: Guard| guard.mirrored_if(left_handed).to_string();
+                setting_checkbox!(ui, ui_state, guard_label(Guard::TagLeft), tag_left);
+                setting_checkbox!(ui, ui_state, guard_label(Guard::TagRight), tag_right);
+                setting_checkbox!(ui, ui_state, guard_label(Guard::TagAbove), tag_above);
+                setting_checkbox!(ui, ui_state, guard_label(Guard::PflugLeft), pflug_left);
+                setting_checkbox!(ui, ui_state, guard_label(Guard::PflugRight), pflug_right);
+                setting_checkbox!(ui, ui_state, guard_label(Guard::OchsLeft), ochs_left);
+                setting_checkbox!(ui, ui_state, guard_label(Guard::OchsRight), ochs_right);
+                setting_checkbox!(ui, ui_state, guard_label(Guard::Eisenport), eisenport);
+                setting_checkbox!(ui, ui_state, guard_label(Guard::Langort), langort);
+                setting_checkbox!(ui, ui_state, guard_label(Guard::Alber), alber);
+                setting_checkbox!(ui, ui_state, "Start from Guard", guard_start);
             }
 
             ui.label("Allowed Strikes:");
@@ -111,6 +399,13 @@ fn update_ui(mut ui_state: ResMut<UiState>, mut contexts: EguiContexts) {
             setting_checkbox!(ui, ui_state, "Fehler", fehler_allowed);
             if ui_state.fehler_allowed {
                 setting_checkbox!(ui, ui_state, "Doppelfehler", doppelfehler_allowed);
+                setting_combo_box!(
+                    ui,
+                    ui_state,
+                    "Fehler Target",
+                    fehler_target,
+                    FehlerTarget::ALL
+                );
             }
 
             ui.add(
@@ -139,8 +434,101 @@ fn update_ui(mut ui_state: ResMut<UiState>, mut contexts: EguiContexts) {
                 ui_state.attack_count_min = ui_state.attack_count_max;
             }
 
+            setting_checkbox!(ui, ui_state, "Left-Handed Fencer", left_handed);
+            setting_checkbox!(ui, ui_state, "Partner Perspective", partner_perspective);
+            setting_checkbox!(ui, ui_state, "Partner Responses", partner_drill);
+            if ui_state.partner_drill {
+                setting_checkbox!(ui, ui_state, Defense::Parry.to_string(), parry_allowed);
+                setting_checkbox!(
+                    ui,
+                    ui_state,
+                    Defense::Versetzen.to_string(),
+                    versetzen_allowed
+                );
+                setting_checkbox!(
+                    ui,
+                    ui_state,
+                    Defense::CounterCut.to_string(),
+                    counter_cut_allowed
+                );
+            }
+            setting_combo_box!(ui, ui_state, "Strike Colors", palette, Palette::ALL);
+            setting_combo_box!(
+                ui,
+                ui_state,
+                "Strike Marker",
+                strike_glyph,
+                StrikeGlyph::ALL
+            );
+            setting_checkbox!(ui, ui_state, "Separate Fencer Window", fencer_window);
+            setting_checkbox!(ui, ui_state, "Sequence Strip", preview_strip);
+            if ui_state.preview_strip {
+                setting_checkbox!(ui, ui_state, "Show Next Drill", queue_next_drill);
+            }
+            setting_combo_box!(
+                ui,
+                ui_state,
+                "Avoid Repeats",
+                repeat_avoidance,
+                RepeatAvoidance::ALL
+            );
+            if ui_state.repeat_avoidance != RepeatAvoidance::Off {
+                ui.add(
+                    egui::Slider::new(&mut ui_state.repeat_window, 1..=DrillHistory::CAPACITY)
+                        .text("Within Last Drills"),
+                );
+                ui.end_row();
+            }
+            ui.label(format!(
+                "Difficulty: {:.1}",
+                DifficultyScore::of(&meyer_cross).total()
+            ));
+            ui.end_row();
+            setting_checkbox!(ui, ui_state, "Adaptive Difficulty", adaptive_difficulty);
+            if ui_state.adaptive_difficulty {
+                ui.label(format!(
+                    "Level {} of {}",
+                    progression.level() + 1,
+                    Progression::LEVELS
+                ));
+                ui.end_row();
+            }
+            setting_checkbox!(ui, ui_state, "Spaced Repetition", spaced_repetition);
+            if (ui_state.adaptive_difficulty || ui_state.spaced_repetition)
+                && ui_state.reaction_measurement
+            {
+                ui.add(
+                    egui::Slider::new(&mut ui_state.reaction_target_s, 0.2..=2.0)
+                        .text("Target Reaction (sec)"),
+                );
+                ui.end_row();
+            }
+            setting_checkbox!(ui, ui_state, "Markov Chain", markov_chain);
+            setting_checkbox!(ui, ui_state, "Walk All Sequences", walk_all_sequences);
+            if ui_state.walk_all_sequences && walk.total() > 0 {
+                ui.label(format!("Sequence {} of {}", walk.position(), walk.total()));
+                ui.end_row();
+            }
+            setting_checkbox!(ui, ui_state, "Terminology Quiz", quiz_mode);
+
+            ui.label("Flash Mode:");
+            if ui.checkbox(&mut ui_state.flash_mode, "").changed() && ui_state.flash_mode {
+                ui_state.timer_active = false;
+            }
+            ui.end_row();
+
+            if ui_state.flash_mode {
+                ui.add(
+                    egui::Slider::new(&mut ui_state.flash_show_s, 1..=30)
+                        .text("Time to Memorize (sec)"),
+                );
+                ui.end_row();
+            }
+
             ui.label("Timer active:");
-            ui.checkbox(&mut ui_state.timer_active, "");
+            if ui.checkbox(&mut ui_state.timer_active, "").changed() && ui_state.timer_active {
+                ui_state.flash_mode = false;
+            }
             ui.end_row();
 
             if ui_state.timer_active {
@@ -149,16 +537,55 @@ fn update_ui(mut ui_state: ResMut<UiState>, mut contexts: EguiContexts) {
                         .text("Time for Preparation (sec)"),
                 );
                 ui.end_row();
-                ui.add(
-                    egui::Slider::new(&mut ui_state.time_per_strike_s, 1..=10)
-                        .text("Time per Strike (sec)"),
-                );
+
+                ui.label("Paused:");
+                let mut paused = countdown.is_paused();
+                if ui.checkbox(&mut paused, "").changed() {
+                    countdown.set_paused(paused);
+                }
                 ui.end_row();
+
+                setting_checkbox!(ui, ui_state, "Random Cue Delay", random_cue_delay);
+                if ui_state.random_cue_delay {
+                    ui.add(
+                        egui::Slider::new(&mut ui_state.cue_delay_min_s, 0.2..=5.0)
+                            .text("Cue Delay Min (sec)"),
+                    );
+                    ui.end_row();
+
+                    if ui_state.cue_delay_min_s > ui_state.cue_delay_max_s {
+                        ui_state.cue_delay_max_s = ui_state.cue_delay_min_s;
+                    }
+
+                    ui.add(
+                        egui::Slider::new(&mut ui_state.cue_delay_max_s, 0.2..=5.0)
+                            .text("Cue Delay Max (sec)"),
+                    );
+                    ui.end_row();
+
+                    if ui_state.cue_delay_max_s < ui_state.cue_delay_min_s {
+                        ui_state.cue_delay_min_s = ui_state.cue_delay_max_s;
+                    }
+                } else {
+                    ui.add(
+                        egui::Slider::new(&mut ui_state.time_per_strike_s, 1..=10)
+                            .text("Time per Strike (sec)"),
+                    );
+                    ui.end_row();
+                }
+
+                setting_checkbox!(
+                    ui,
+                    ui_state,
+                    "Reaction Measurement (Space)",
+                    reaction_measurement
+                );
             }
         });
 
         if ui.button("Apply").clicked() {
             ui_state.apply_pending = true;
+            ui_state.rules_changed = true;
         }
     });
 }
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    meyer_cross::share::ShareLink,
+    resources::{drill_rng::DrillRng, meyer_cross::MeyerCross},
+};
+
+use super::setup_egui::UiState;
+
+#[derive(Default)]
+pub struct SharePanelState {
+    include_rules: bool,
+    include_seed: bool,
+    /// The seed in the link, drawn anew once the drill changes.
+    shared_seed: Option<u64>,
+    pasted_link: String,
+    status: String,
+}
+
+/// Shows the shared drill instead of generating a new one, the rules and seed are taken over
+/// when the link carries them.
+fn open_link(
+    link: ShareLink,
+    meyer_cross: &mut MeyerCross,
+    ui_state: &mut UiState,
+    drill_rng: &mut DrillRng,
+) {
+    meyer_cross.replace(link.drill);
+    if let Some(rules) = &link.rules {
+        ui_state.apply_rules(rules);
+    }
+    if let Some(seed) = link.seed {
+        drill_rng.reseed(seed);
+    }
+    ui_state.apply_pending = false;
+}
+
+/// The page address without query and fragment, empty outside the browser.
+#[cfg(target_arch = "wasm32")]
+fn page_url() -> String {
+    web_sys::window()
+        .and_then(|window| {
+            let location = window.location();
+            Some(location.origin().ok()? + &location.pathname().ok()?)
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn page_url() -> String {
+    String::new()
+}
+
+/// Opens the drill from the page address the web build was loaded with.
+#[cfg(target_arch = "wasm32")]
+pub fn load_shared_drill(
+    mut meyer_cross: ResMut<MeyerCross>,
+    mut ui_state: ResMut<UiState>,
+    mut drill_rng: ResMut<DrillRng>,
+) {
+    let Some(location) = web_sys::window().map(|window| window.location()) else {
+        return;
+    };
+    let link = [location.hash(), location.search()]
+        .into_iter()
+        .flatten()
+        .find_map(|text| ShareLink::decode(&text).ok());
+    if let Some(link) = link {
+        open_link(link, &mut meyer_cross, &mut ui_state, &mut drill_rng);
+    }
+}
+
+pub fn update_share_panel(
+    mut meyer_cross: ResMut<MeyerCross>,
+    mut ui_state: ResMut<UiState>,
+    mut drill_rng: ResMut<DrillRng>,
+    mut state: Local<SharePanelState>,
+    mut contexts: EguiContexts,
+) {
+    let ctx = contexts.ctx_mut();
+    egui::Window::new("Share")
+        .default_open(false)
+        .show(ctx, |ui| {
+            ui.checkbox(&mut state.include_rules, "Include Rules");
+            ui.checkbox(&mut state.include_seed, "Include Seed");
+
+            if !state.include_seed || meyer_cross.is_changed() {
+                state.shared_seed = None;
+            }
+            if state.include_seed && state.shared_seed.is_none() {
+                state.shared_seed = Some(drill_rng.fresh_seed());
+            }
+            let link = ShareLink {
+                drill: meyer_cross.clone(),
+                rules: state.include_rules.then(|| ui_state.rules()),
+                seed: state.shared_seed,
+            };
+            let mut url = format!("{}#{}", page_url(), link.encode());
+            ui.add(egui::TextEdit::singleline(&mut url).desired_width(f32::INFINITY));
+            if ui.button("Copy Link").clicked() {
+                ui.output_mut(|output| output.copied_text = url.clone());
+                #[cfg(target_arch = "wasm32")]
+                if let Some(history) = web_sys::window().and_then(|w| w.history().ok()) {
+                    let _ = history.replace_state_with_url(
+                        &wasm_bindgen::JsValue::NULL,
+                        "",
+                        Some(&url),
+                    );
+                }
+                state.status = "Link copied".to_string();
+            }
+
+            ui.separator();
+            ui.text_edit_singleline(&mut state.pasted_link);
+            if ui.button("Open Link").clicked() {
+                state.status = match ShareLink::decode(&state.pasted_link) {
+                    Ok(link) => {
+                        open_link(link, &mut meyer_cross, &mut ui_state, &mut drill_rng);
+                        "Drill opened".to_string()
+                    }
+                    Err(e) => e.to_string(),
+                };
+            }
+            ui.label(&state.status);
+        });
+}
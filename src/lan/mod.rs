@@ -0,0 +1,203 @@
+use std::{
+    io,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4, ToSocketAddrs, UdpSocket},
+};
+
+use crate::{
+    meyer_cross::{share::ShareLink, MeyerCross},
+    resources::countdown::CountdownPhase,
+};
+
+/// Port the clients listen on for the host's broadcasts.
+pub const DEFAULT_PORT: u16 = 8348;
+
+/// Marks the packets, so other traffic on the port is ignored.
+const PROTOCOL: &str = "meyer-cross-sync/1";
+
+/// What every station shows, sent by the host whenever it changes and as heartbeat.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SyncState {
+    /// Increases with every generated drill, so a repeated sequence still restarts the clients.
+    pub drill_version: u64,
+    pub drill: MeyerCross,
+    pub phase: CountdownPhase,
+    pub revealed: usize,
+}
+
+fn phase_code(phase: CountdownPhase) -> u8 {
+    match phase {
+        CountdownPhase::Inactive => 0,
+        CountdownPhase::Preparation => 1,
+        CountdownPhase::Attacks => 2,
+        CountdownPhase::Done => 3,
+    }
+}
+
+fn phase_from_code(code: u8) -> Option<CountdownPhase> {
+    match code {
+        0 => Some(CountdownPhase::Inactive),
+        1 => Some(CountdownPhase::Preparation),
+        2 => Some(CountdownPhase::Attacks),
+        3 => Some(CountdownPhase::Done),
+        _ => None,
+    }
+}
+
+impl SyncState {
+    /// Encodes as `meyer-cross-sync/1 <version> <phase> <revealed> <drill link>`.
+    pub fn encode(&self) -> String {
+        let link = ShareLink {
+            drill: self.drill.clone(),
+            rules: None,
+            seed: None,
+        };
+        format!(
+            "{PROTOCOL} {} {} {} {}",
+            self.drill_version,
+            phase_code(self.phase),
+            self.revealed,
+            link.encode()
+        )
+    }
+
+    pub fn decode(text: &str) -> Option<Self> {
+        let mut parts = text.split(' ');
+        if parts.next()? != PROTOCOL {
+            return None;
+        }
+        Some(SyncState {
+            drill_version: parts.next()?.parse().ok()?,
+            phase: phase_from_code(parts.next()?.parse().ok()?)?,
+            revealed: parts.next()?.parse().ok()?,
+            drill: ShareLink::decode(parts.next()?).ok()?.drill,
+        })
+    }
+}
+
+/// Sends the state to all clients, by default as broadcast into the local network.
+pub struct LanHost {
+    socket: UdpSocket,
+    targets: Vec<SocketAddr>,
+}
+
+impl LanHost {
+    pub fn broadcast(port: u16) -> io::Result<Self> {
+        Self::new([SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::BROADCAST, port))])
+    }
+
+    pub fn new(targets: impl IntoIterator<Item = SocketAddr>) -> io::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_broadcast(true)?;
+        Ok(LanHost {
+            socket,
+            targets: targets.into_iter().collect(),
+        })
+    }
+
+    pub fn send(&self, state: &SyncState) -> io::Result<()> {
+        let packet = state.encode();
+        for target in &self.targets {
+            self.socket.send_to(packet.as_bytes(), target)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the host's state without blocking the frame.
+pub struct LanClient {
+    socket: UdpSocket,
+}
+
+impl LanClient {
+    pub fn listen(port: u16) -> io::Result<Self> {
+        Self::bind((Ipv4Addr::UNSPECIFIED, port))
+    }
+
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_nonblocking(true)?;
+        Ok(LanClient { socket })
+    }
+
+    /// The newest state received since the last call, older packets are outdated anyway.
+    pub fn receive(&self) -> Option<SyncState> {
+        let mut buffer = [0; 1024];
+        let mut latest = None;
+        while let Ok(length) = self.socket.recv(&mut buffer) {
+            if let Some(state) = std::str::from_utf8(&buffer[..length])
+                .ok()
+                .and_then(SyncState::decode)
+            {
+                latest = Some(state);
+            }
+        }
+        latest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::*;
+    use crate::meyer_cross::rules::DrillRules;
+
+    fn receive(client: &LanClient) -> Option<SyncState> {
+        (0..50).find_map(|_| {
+            thread::sleep(Duration::from_millis(10));
+            client.receive()
+        })
+    }
+
+    #[test]
+    fn state_survives_encoding() {
+        let rules = DrillRules {
+            max_length: 8,
+            ..DrillRules::default()
+        };
+        let state = SyncState {
+            drill_version: 7,
+            drill: MeyerCross::random(&mut rand::thread_rng(), &rules).unwrap(),
+            phase: CountdownPhase::Attacks,
+            revealed: 3,
+        };
+        assert_eq!(SyncState::decode(&state.encode()), Some(state));
+        assert_eq!(SyncState::decode("other-protocol 1 2 3 drill=s3_al"), None);
+    }
+
+    #[test]
+    fn clients_follow_host_over_loopback() {
+        let clients = [
+            LanClient::bind("127.0.0.1:0").unwrap(),
+            LanClient::bind("127.0.0.1:0").unwrap(),
+        ];
+        let host = LanHost::new(clients.iter().map(|c| c.socket.local_addr().unwrap())).unwrap();
+        let mut rng = rand::thread_rng();
+        let rules = DrillRules {
+            min_length: 2,
+            max_length: 6,
+            ..DrillRules::default()
+        };
+
+        for drill_version in 1..=3 {
+            let drill = MeyerCross::random(&mut rng, &rules).unwrap();
+            let attack_count = drill.attack_sequence.as_ref().map_or(0, Vec::len);
+            let beats = std::iter::once((CountdownPhase::Preparation, 0))
+                .chain((0..=attack_count).map(|revealed| (CountdownPhase::Attacks, revealed)))
+                .chain(std::iter::once((CountdownPhase::Done, attack_count)));
+
+            for (phase, revealed) in beats {
+                let state = SyncState {
+                    drill_version,
+                    drill: drill.clone(),
+                    phase,
+                    revealed,
+                };
+                host.send(&state).unwrap();
+                for client in &clients {
+                    assert_eq!(receive(client).as_ref(), Some(&state));
+                }
+            }
+        }
+    }
+}
@@ -1,139 +1,260 @@
+mod cli;
+mod export;
 mod gui;
+#[cfg(not(target_arch = "wasm32"))]
+mod lan;
 mod meyer_cross;
 mod plugins;
+#[cfg(feature = "remote")]
+mod remote;
+// Drawing helpers shared by the GUI and the export, free of Bevy.
+mod render;
 mod resources;
 
-use std::collections::HashSet;
-
-use bevy::prelude::*;
-use gui::setup_egui::{MeyerUiPlugin, UiState};
-use meyer_cross::{
-    preparation::StreichenCount,
-    types::{Guard, Opening},
+use bevy::{prelude::*, window::ExitCondition};
+use gui::{
+    attack_indicators::{
+        draw_fehler_paths, position_attack_indicators, spawn_indicators, update_cross_layout,
+        update_preparation_indicator, CrossLayout,
+    },
+    preview_strip::{draw_strip_arrows, spawn_strip_labels, update_strip_labels},
+    response_indicators::{position_response_indicators, spawn_response_indicators},
+    setup_egui::{MeyerUiPlugin, UiState},
 };
-use resources::meyer_cross::{
-    MeyerCross, Preparation,
-    Preparation::{Static, Streichen},
+use resources::{
+    drill_rng::DrillRng,
+    meyer_cross::{MeyerCross, QueuedDrill, SequenceWalk},
+    profile::TrainingProfile,
 };
 
-use crate::meyer_cross::{attack_sequence::AttackSequenceLength, types::Strike};
-
 fn main() {
-    App::new()
-        .add_plugins((DefaultPlugins, plugins::meyer_cross::MeyerCrossPlugin))
-        .add_plugins(MeyerUiPlugin)
-        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
-        .add_systems(Startup, setup)
-        .add_systems(Update, apply_ui_selections)
-        .add_systems(Update, position_attack_indicators)
-        .run();
+    match cli::parse(std::env::args().skip(1)) {
+        Ok(cli::Command::Run { remote_lan }) => run_app(remote_lan),
+        Ok(cli::Command::Export {
+            output,
+            drills,
+            rules,
+        }) => {
+            if let Err(e) = cli::export(output, drills, &rules) {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+        Ok(cli::Command::Stats {
+            samples,
+            seed,
+            rules,
+        }) => {
+            if let Err(e) = cli::stats(samples, seed, &rules) {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("{e}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    }
 }
 
-#[derive(Component)]
-struct AttackIndicator(usize);
+#[cfg_attr(not(feature = "remote"), allow(unused_variables))]
+fn run_app(remote_lan: bool) {
+    let mut app = App::new();
+    app.add_plugins((
+        DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "Meyer Cross".into(),
+                // Lets the wasm build follow the browser size, including portrait phones.
+                fit_canvas_to_parent: true,
+                ..default()
+            }),
+            exit_condition: ExitCondition::OnPrimaryClosed,
+            ..default()
+        }),
+        plugins::meyer_cross::MeyerCrossPlugin,
+        plugins::countdown::CountdownPlugin,
+        plugins::reaction::ReactionPlugin,
+        plugins::flash::FlashPlugin,
+        plugins::adaptive::AdaptivePlugin,
+        plugins::repetition::RepetitionPlugin,
+    ))
+    .add_plugins(MeyerUiPlugin)
+    .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+    .insert_resource(CrossLayout::default())
+    .add_systems(
+        Startup,
+        (
+            setup,
+            spawn_indicators,
+            spawn_strip_labels,
+            spawn_response_indicators,
+        ),
+    )
+    .add_systems(Update, apply_ui_selections)
+    .add_systems(Update, update_cross_layout)
+    .add_systems(
+        Update,
+        (
+            position_attack_indicators,
+            position_response_indicators,
+            update_preparation_indicator,
+            draw_fehler_paths,
+            update_strip_labels,
+            draw_strip_arrows,
+        )
+            .after(update_cross_layout),
+    );
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let font = asset_server.load("OpenSans-Regular.ttf");
-    let text_style = TextStyle {
-        font: font.clone(),
-        font_size: 150.0,
-        color: Color::WHITE,
-    };
+    // Browsers have no UDP sockets.
+    #[cfg(not(target_arch = "wasm32"))]
+    app.add_plugins(plugins::lan_sync::LanSyncPlugin);
 
-    commands.spawn(Camera2dBundle::default());
+    #[cfg(feature = "remote")]
+    app.add_plugins(plugins::remote::RemotePlugin::new(remote_lan));
 
-    for id in 1..=8 {
-        commands.spawn((
-            Text2dBundle {
-                text: Text::from_section(id.to_string(), text_style.clone())
-                    .with_alignment(TextAlignment::Center),
-                ..default()
-            },
-            AttackIndicator(id),
-        ));
-    }
+    app.run();
 }
 
-const DIST: f32 = 250.0;
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
 
-fn apply_ui_selections(mut meyer_cross: ResMut<MeyerCross>, mut ui: ResMut<UiState>) {
+fn apply_ui_selections(
+    mut meyer_cross: ResMut<MeyerCross>,
+    mut queued: ResMut<QueuedDrill>,
+    mut walk: ResMut<SequenceWalk>,
+    mut drill_rng: ResMut<DrillRng>,
+    profile: Res<TrainingProfile>,
+    mut ui: ResMut<UiState>,
+) {
     if ui.apply_pending {
         ui.apply_pending = false;
     } else {
         return;
     }
 
-    let mut preps = HashSet::new();
-    fn insert_prep(preps: &mut HashSet<Preparation>, prep: Preparation, cond: bool) {
-        if cond {
-            preps.insert(prep);
+    // A queued drill generated with outdated rules is thrown away.
+    let rules_changed = std::mem::take(&mut ui.rules_changed);
+    let rules = ui.rules();
+    if ui.walk_all_sequences {
+        if rules_changed {
+            walk.restart(&rules);
+        }
+        if let Some(drill) = walk
+            .next_sequence(&rules)
+            .and_then(|sequence| MeyerCross::with_sequence(drill_rng.rng(), &rules, sequence).ok())
+        {
+            meyer_cross.replace(drill);
+        }
+        return;
+    }
+
+    let spaced_repetition = ui.spaced_repetition;
+    let mut randomize = |drill: &mut MeyerCross| {
+        if spaced_repetition {
+            drill.randomize_with_schedule(drill_rng.rng(), &rules, &profile.schedule)
+        } else {
+            drill.randomize_with_rules(drill_rng.rng(), &rules)
+        }
+    };
+    match queued.drill.take() {
+        Some(next) if ui.queue_next_drill && !rules_changed => meyer_cross.replace(next),
+        _ => {
+            let _ = randomize(&mut meyer_cross);
         }
     }
+    if ui.queue_next_drill {
+        // Generated after the current drill, so it avoids repeating it and its history.
+        let mut next = meyer_cross.clone();
+        queued.drill = randomize(&mut next).ok().map(|()| next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if ui.random_streichen {
-        preps.insert(Streichen(StreichenCount::One));
-        preps.insert(Streichen(StreichenCount::Two));
-        preps.insert(Streichen(StreichenCount::Three));
-    } else if ui.streichen {
-        preps.insert(Streichen(StreichenCount::Three));
+    fn drill_app(seed: u64) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(MeyerCross::new())
+            .insert_resource(QueuedDrill::default())
+            .insert_resource(SequenceWalk::default())
+            .insert_resource(DrillRng::from_seed(seed))
+            .insert_resource(TrainingProfile::default())
+            .insert_resource(UiState::new())
+            .add_systems(Update, apply_ui_selections);
+        app
     }
 
-    insert_prep(&mut preps, Static(Guard::TagLeft), ui.tag_left);
-    insert_prep(&mut preps, Static(Guard::TagRight), ui.tag_right);
-    insert_prep(&mut preps, Static(Guard::TagAbove), ui.tag_above);
-    insert_prep(&mut preps, Static(Guard::PflugLeft), ui.pflug_left);
-    insert_prep(&mut preps, Static(Guard::PflugRight), ui.pflug_right);
-    insert_prep(&mut preps, Static(Guard::OchsLeft), ui.ochs_left);
-    insert_prep(&mut preps, Static(Guard::OchsRight), ui.ochs_right);
-    insert_prep(&mut preps, Static(Guard::Eisenport), ui.eisenport);
-    insert_prep(&mut preps, Static(Guard::Langort), ui.langort);
-    insert_prep(&mut preps, Static(Guard::Alber), ui.alber);
-
-    let mut strikes = HashSet::new();
-    fn insert_strike(strikes: &mut HashSet<Strike>, strike: Strike, condition: bool) {
-        if condition {
-            strikes.insert(strike);
-        }
+    fn drill(app: &App) -> MeyerCross {
+        app.world.resource::<MeyerCross>().clone()
     }
 
-    insert_strike(&mut strikes, Strike::Long, ui.long_edge_allowed);
-    insert_strike(&mut strikes, Strike::Short, ui.short_edge_allowed);
-    insert_strike(&mut strikes, Strike::Flat, ui.flat_allowed);
-    insert_strike(&mut strikes, Strike::Fehler, ui.fehler_allowed);
-
-    let _ = meyer_cross.randomize(
-        AttackSequenceLength::randomized_range(ui.attack_count_min, ui.attack_count_max)
-            .expect("Attack Sequence Length out of bounds"),
-        &preps,
-        &strikes,
-        ui.doppelfehler_allowed,
-    );
-}
+    #[test]
+    fn apply_regenerates_the_drill() {
+        let mut app = drill_app(3);
+        let rules = app.world.resource::<UiState>().rules();
+        let mut expected_rng = DrillRng::from_seed(3);
 
-fn position_attack_indicators(
-    meyer_cross: Res<MeyerCross>,
-    mut query: Query<(&mut Transform, &mut Text, &mut Visibility, &AttackIndicator)>,
-) {
-    if let Some(attack_sequence) = meyer_cross.attack_sequence.as_ref() {
-        for (mut transform, mut text, mut visibility, attack_indicator) in query.iter_mut() {
-            if let Some(attack) = attack_sequence.get(attack_indicator.0 - 1) {
-                *visibility = Visibility::Visible;
-                let inner_pos_factor = if attack_indicator.0 > 4 { 0.5 } else { 1.0 };
-                transform.translation = match attack.opening {
-                    Opening::TopLeft => Vec3::new(-DIST, DIST, 0.0),
-                    Opening::TopRight => Vec3::new(DIST, DIST, 0.0),
-                    Opening::BottomLeft => Vec3::new(-DIST, -DIST, 0.0),
-                    Opening::BottomRight => Vec3::new(DIST, -DIST, 0.0),
-                } * inner_pos_factor;
-                text.sections[0].style.color = match attack.strike {
-                    Strike::Flat => Color::RED,
-                    Strike::Long => Color::WHITE,
-                    Strike::Short => Color::BLUE,
-                    Strike::Fehler => Color::GREEN,
-                };
-            } else {
-                *visibility = Visibility::Hidden;
-            }
+        app.update();
+        assert!(!app.world.resource::<UiState>().apply_pending);
+        let first = drill(&app);
+        assert_eq!(
+            first,
+            MeyerCross::random(expected_rng.rng(), &rules).unwrap()
+        );
+
+        app.update();
+        assert_eq!(drill(&app), first);
+
+        app.world.resource_mut::<UiState>().apply_pending = true;
+        app.update();
+        assert_eq!(
+            drill(&app),
+            MeyerCross::random(expected_rng.rng(), &rules).unwrap()
+        );
+    }
+
+    #[test]
+    fn apply_uses_the_changed_rules() {
+        let mut app = drill_app(5);
+        app.update();
+
+        let mut ui = app.world.resource_mut::<UiState>();
+        ui.streichen = false;
+        ui.huten = true;
+        ui.alber = true;
+        ui.attack_count_min = 8;
+        ui.attack_count_max = 8;
+        ui.apply_pending = true;
+        app.update();
+
+        let drill = drill(&app);
+        assert_eq!(
+            drill.preparation,
+            Some(meyer_cross::preparation::Preparation::Static(
+                meyer_cross::types::Guard::Alber
+            ))
+        );
+        assert_eq!(drill.attack_sequence.map(|s| s.len()), Some(8));
+    }
+
+    #[test]
+    fn walk_steps_through_all_sequences() {
+        let mut app = drill_app(7);
+        app.world.resource_mut::<UiState>().walk_all_sequences = true;
+
+        let mut sequences = vec![];
+        for _ in 0..5 {
+            app.world.resource_mut::<UiState>().apply_pending = true;
+            app.update();
+            sequences.push(drill(&app).attack_sequence.unwrap());
         }
+
+        // Long edge only with four attacks leaves one sequence per base sequence.
+        assert_eq!(app.world.resource::<SequenceWalk>().total(), 4);
+        assert!((0..4).all(|i| !sequences[i + 1..4].contains(&sequences[i])));
+        assert_eq!(sequences[4], sequences[0]);
     }
 }
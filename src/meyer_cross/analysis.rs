@@ -0,0 +1,458 @@
+use std::fmt;
+
+use rand::Rng;
+
+use super::{
+    preparation::Preparation,
+    rules::DrillRules,
+    types::{Opening, Strike},
+    MeyerCross, MeyerCrossError,
+};
+
+/// Observed counts per value, with the share each value should get from a fair generator.
+#[derive(Clone, Debug)]
+pub struct Tally<T> {
+    pub entries: Vec<TallyEntry<T>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TallyEntry<T> {
+    pub value: T,
+    pub count: usize,
+    pub expected_share: f64,
+}
+
+/// Pearson's chi-square test of the observed counts against the expected shares.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ChiSquare {
+    pub statistic: f64,
+    pub degrees_of_freedom: usize,
+    /// Probability of a deviation at least this large if the generator were fair.
+    pub p_value: f64,
+}
+
+impl<T: PartialEq> Tally<T> {
+    /// Expects every value equally often.
+    pub fn uniform(values: impl IntoIterator<Item = T>) -> Self {
+        Self::weighted(values.into_iter().map(|value| (value, 1.0)))
+    }
+
+    /// Expects the values in proportion to their weights.
+    pub fn weighted(weights: impl IntoIterator<Item = (T, f64)>) -> Self {
+        let entries = weights.into_iter().collect::<Vec<_>>();
+        let total = entries.iter().map(|(_, weight)| weight).sum::<f64>();
+        Tally {
+            entries: entries
+                .into_iter()
+                .map(|(value, weight)| TallyEntry {
+                    value,
+                    count: 0,
+                    expected_share: if total > 0.0 { weight / total } else { 0.0 },
+                })
+                .collect(),
+        }
+    }
+
+    /// Counts the value, values without expectation are added with an expected share of zero.
+    pub fn add(&mut self, value: T) {
+        match self.entries.iter_mut().find(|entry| entry.value == value) {
+            Some(entry) => entry.count += 1,
+            None => self.entries.push(TallyEntry {
+                value,
+                count: 1,
+                expected_share: 0.0,
+            }),
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.entries.iter().map(|entry| entry.count).sum()
+    }
+
+    /// Values without expected share are left out, they would make any deviation infinite.
+    pub fn chi_square(&self) -> ChiSquare {
+        let total = self.total() as f64;
+        let expected = self
+            .entries
+            .iter()
+            .filter(|entry| entry.expected_share > 0.0)
+            .collect::<Vec<_>>();
+        let statistic = expected
+            .iter()
+            .map(|entry| {
+                let expected_count = entry.expected_share * total;
+                (entry.count as f64 - expected_count).powi(2) / expected_count
+            })
+            .sum::<f64>();
+        let degrees_of_freedom = expected.len().saturating_sub(1);
+        ChiSquare {
+            statistic,
+            degrees_of_freedom,
+            p_value: if degrees_of_freedom == 0 || total == 0.0 {
+                1.0
+            } else {
+                upper_regularized_gamma(degrees_of_freedom as f64 / 2.0, statistic / 2.0)
+            },
+        }
+    }
+}
+
+/// Natural logarithm of the gamma function, Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+    let tmp = x + 5.5 - (x + 0.5) * (x + 5.5).ln();
+    let series = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .fold(1.000_000_000_190_015, |sum, (i, c)| {
+            sum + c / (x + 1.0 + i as f64)
+        });
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+/// Q(a, x), the chi-square survival function for a = df / 2 and x = statistic / 2.
+fn upper_regularized_gamma(a: f64, x: f64) -> f64 {
+    const ITERATIONS: usize = 200;
+    const EPSILON: f64 = 1e-12;
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefactor = (-x + a * x.ln() - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        // Series of the lower function P(a, x).
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..ITERATIONS {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        (1.0 - sum * prefactor).clamp(0.0, 1.0)
+    } else {
+        // Continued fraction of Q(a, x), modified Lentz's method.
+        let tiny = f64::MIN_POSITIVE / EPSILON;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut fraction = d;
+        for n in 1..ITERATIONS {
+            let an = -(n as f64) * (n as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            fraction *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        (fraction * prefactor).clamp(0.0, 1.0)
+    }
+}
+
+/// Share of each strike at every attack of a sequence of `length`, if the generator picks
+/// uniformly among the strikes allowed there: no Fehler last, nor right after a Fehler unless
+/// Doppelfehler are enabled.
+fn expected_strike_shares(
+    strikes: &[Strike],
+    length: usize,
+    doppelfehler_enabled: bool,
+) -> Vec<Vec<f64>> {
+    let true_strikes = strikes.iter().filter(|&&s| s != Strike::Fehler).count();
+    let pick = |fehler_allowed: bool| {
+        let choices = if fehler_allowed {
+            strikes.len()
+        } else {
+            true_strikes
+        };
+        strikes
+            .iter()
+            .map(|&strike| {
+                if choices == 0 || (strike == Strike::Fehler && !fehler_allowed) {
+                    0.0
+                } else {
+                    1.0 / choices as f64
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    // Probability that the previous attack was a Fehler.
+    let mut after_fehler = 0.0;
+    (0..length)
+        .map(|n| {
+            let last = n + 1 == length;
+            let shares = pick(!last)
+                .into_iter()
+                .zip(pick(!last && doppelfehler_enabled))
+                .map(|(fresh, repeated)| (1.0 - after_fehler) * fresh + after_fehler * repeated)
+                .collect::<Vec<_>>();
+            after_fehler = strikes
+                .iter()
+                .zip(&shares)
+                .filter(|(&strike, _)| strike == Strike::Fehler)
+                .map(|(_, share)| share)
+                .sum();
+            shares
+        })
+        .collect()
+}
+
+/// How often the generator picks each strike, opening and preparation for the given rules.
+/// A fair generator would use the strikes allowed at each position equally, which leaves
+/// Fehler out of the last attack and, without Doppelfehler, out of the attack after a Fehler,
+/// so the first attack gets the most Fehler. The chi-square tests show how far it is off.
+#[derive(Clone, Debug)]
+pub struct DistributionReport {
+    pub samples: usize,
+    pub strikes: Tally<Strike>,
+    pub openings: Tally<Opening>,
+    pub preparations: Tally<Preparation>,
+    pub lengths: Tally<usize>,
+    /// Strikes by attack position, the first entry is the first attack.
+    pub positions: Vec<Tally<Strike>>,
+    /// Fehler by attack number, 1 being the first attack.
+    pub fehler_positions: Tally<usize>,
+}
+
+impl DistributionReport {
+    pub fn sample<R: Rng + ?Sized>(
+        rng: &mut R,
+        rules: &DrillRules,
+        samples: usize,
+    ) -> Result<Self, MeyerCrossError> {
+        let mut strikes = Strike::ALL.to_vec();
+        strikes.retain(|strike| rules.strikes.contains(strike));
+        let mut preparations = rules.preparations.iter().copied().collect::<Vec<_>>();
+        preparations.sort();
+        let lengths = rules.min_length..=rules.max_length;
+
+        // Lengths are equally likely, so every length adds its shares with the same weight.
+        let shares = lengths
+            .clone()
+            .map(|length| expected_strike_shares(&strikes, length, rules.doppelfehler_enabled))
+            .collect::<Vec<_>>();
+        // The expected strikes at the attack with the index, or at all attacks.
+        let expected = |index: Option<usize>| {
+            Tally::weighted(strikes.iter().enumerate().map(|(i, &strike)| {
+                let weight = shares
+                    .iter()
+                    .flat_map(|sequence| match index {
+                        Some(index) => sequence.get(index..=index).unwrap_or_default(),
+                        None => sequence,
+                    })
+                    .map(|position| position[i])
+                    .sum();
+                (strike, weight)
+            }))
+        };
+
+        let mut report = DistributionReport {
+            samples,
+            strikes: expected(None),
+            openings: Tally::uniform(Opening::ALL),
+            preparations: Tally::uniform(preparations),
+            lengths: Tally::uniform(lengths.clone()),
+            positions: (0..lengths.clone().last().unwrap_or_default())
+                .map(|n| expected(Some(n)))
+                .collect(),
+            fehler_positions: match strikes.iter().position(|&s| s == Strike::Fehler) {
+                Some(fehler) => Tally::weighted((0..rules.max_length).map(|index| {
+                    let weight = shares
+                        .iter()
+                        .filter_map(|sequence| sequence.get(index))
+                        .map(|position| position[fehler])
+                        .sum();
+                    (index + 1, weight)
+                })),
+                None => Tally::uniform([]),
+            },
+        };
+
+        for _ in 0..samples {
+            let drill = MeyerCross::random(rng, rules)?;
+            if let Some(preparation) = drill.preparation {
+                report.preparations.add(preparation);
+            }
+            let sequence = drill.attack_sequence.unwrap_or_default();
+            report.lengths.add(sequence.len());
+            for (index, attack) in sequence.iter().enumerate() {
+                report.strikes.add(attack.strike);
+                report.openings.add(attack.opening);
+                report.positions[index].add(attack.strike);
+                if attack.strike == Strike::Fehler {
+                    report.fehler_positions.add(index + 1);
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+fn write_tally<T: PartialEq>(
+    f: &mut fmt::Formatter<'_>,
+    title: &str,
+    tally: &Tally<T>,
+    label: impl Fn(&T) -> String,
+) -> fmt::Result {
+    let total = tally.total().max(1) as f64;
+    writeln!(
+        f,
+        "{title:<24}{:>8}{:>9}{:>10}",
+        "count", "share", "expected"
+    )?;
+    for entry in &tally.entries {
+        writeln!(
+            f,
+            "  {:<22}{:>8}{:>8.1}%{:>9.1}%",
+            label(&entry.value),
+            entry.count,
+            entry.count as f64 / total * 100.0,
+            entry.expected_share * 100.0
+        )?;
+    }
+    let chi_square = tally.chi_square();
+    writeln!(
+        f,
+        "  chi-square {:.2} with {} df, p = {:.4}\n",
+        chi_square.statistic, chi_square.degrees_of_freedom, chi_square.p_value
+    )
+}
+
+impl fmt::Display for DistributionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} drills, {} attacks\n",
+            self.samples,
+            self.strikes.total()
+        )?;
+        write_tally(f, "Strikes", &self.strikes, Strike::to_string)?;
+        write_tally(f, "Openings", &self.openings, Opening::to_string)?;
+        write_tally(
+            f,
+            "Preparations",
+            &self.preparations,
+            Preparation::to_string,
+        )?;
+        write_tally(f, "Lengths", &self.lengths, usize::to_string)?;
+        for (index, position) in self.positions.iter().enumerate() {
+            write_tally(
+                f,
+                &format!("Strikes at Attack {}", index + 1),
+                position,
+                Strike::to_string,
+            )?;
+        }
+        if self.fehler_positions.entries.is_empty() {
+            return Ok(());
+        }
+        write_tally(
+            f,
+            "Fehler by Attack",
+            &self.fehler_positions,
+            usize::to_string,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::meyer_cross::types::Guard;
+
+    #[test]
+    fn p_values_match_the_chi_square_table() {
+        for (statistic, degrees_of_freedom, p_value) in [
+            (3.841, 1.0, 0.05),
+            (6.635, 1.0, 0.01),
+            (7.815, 3.0, 0.05),
+            (18.307, 10.0, 0.05),
+            (0.584, 3.0, 0.9),
+        ] {
+            let q = upper_regularized_gamma(degrees_of_freedom / 2.0, statistic / 2.0);
+            assert!((q - p_value).abs() < 1e-3, "{statistic}: {q} != {p_value}");
+        }
+    }
+
+    #[test]
+    fn openings_and_preparations_are_uniform() {
+        let rules = DrillRules {
+            preparations: Guard::ALL.map(Preparation::Static).into_iter().collect(),
+            strikes: Strike::ALL.into_iter().collect(),
+            min_length: 2,
+            max_length: 8,
+            ..DrillRules::default()
+        };
+        let report =
+            DistributionReport::sample(&mut StdRng::seed_from_u64(1), &rules, 4000).unwrap();
+        assert!(report.openings.chi_square().p_value > 0.001);
+        assert!(report.preparations.chi_square().p_value > 0.001);
+        assert!(report.lengths.chi_square().p_value > 0.001);
+        assert_eq!(report.lengths.total(), 4000);
+        assert!(report
+            .positions
+            .iter()
+            .all(|position| position.chi_square().p_value > 0.001));
+    }
+
+    #[test]
+    fn the_last_attack_is_never_a_fehler() {
+        let rules = DrillRules {
+            strikes: Strike::ALL.into_iter().collect(),
+            ..DrillRules::default()
+        };
+        let report =
+            DistributionReport::sample(&mut StdRng::seed_from_u64(2), &rules, 500).unwrap();
+        let last = report
+            .fehler_positions
+            .entries
+            .iter()
+            .find(|e| e.value == 4);
+        assert!(last.is_none_or(|entry| entry.count == 0 && entry.expected_share == 0.0));
+        assert!(report.strikes.chi_square().p_value > 0.001);
+        let fehler_last = report.positions[3]
+            .entries
+            .iter()
+            .find(|e| e.value == Strike::Fehler);
+        assert!(fehler_last.is_some_and(|entry| entry.expected_share == 0.0));
+    }
+    #[test]
+    fn fehler_are_expected_where_the_rules_allow_them() {
+        let rules = DrillRules {
+            strikes: Strike::ALL.into_iter().collect(),
+            min_length: 4,
+            max_length: 6,
+            doppelfehler_enabled: false,
+            ..DrillRules::default()
+        };
+        let report =
+            DistributionReport::sample(&mut StdRng::seed_from_u64(3), &rules, 20000).unwrap();
+        let shares = report
+            .fehler_positions
+            .entries
+            .iter()
+            .map(|entry| entry.expected_share)
+            .collect::<Vec<_>>();
+        assert!(shares[0] > shares[1]);
+        assert_eq!(shares[5], 0.0);
+        assert!(report.fehler_positions.chi_square().p_value > 0.001);
+    }
+}
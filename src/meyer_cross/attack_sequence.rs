@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use super::types::{Attack, Opening, Strike};
+use super::types::{Attack, Opening, Strike, Transition};
 use rand::{seq::SliceRandom, Rng};
 use thiserror::Error;
 
@@ -8,7 +8,9 @@ pub type AttackSequence = Vec<Attack>;
 pub struct AttackSequenceGenerator;
 
 pub trait GenerateAttackSequence {
-    fn gen_random_attack_sequence(
+    fn gen_random_attack_sequence<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
         length: AttackSequenceLength,
         allowed_strikes: &HashSet<Strike>,
         doppelfehler_enabled: bool,
@@ -24,6 +26,8 @@ pub enum AttackSequenceError {
     #[error("Only Fehler available.")]
     OnlyFehlerNotAllowed,
 }
+
+#[derive(Clone, Copy, Debug)]
 pub struct AttackSequenceLength {
     length: usize,
 }
@@ -53,8 +57,18 @@ impl AttackSequenceLength {
         Self::new(rand::thread_rng().gen_range(Self::MIN_LENGTH..=Self::MAX_LENGTH)).unwrap()
     }
 
-    pub fn randomized_range(min: usize, max: usize) -> Result<Self, AttackSequenceError> {
-        Self::new(rand::thread_rng().gen_range(min..=max))
+    pub fn randomized_range<R: Rng + ?Sized>(
+        rng: &mut R,
+        min: usize,
+        max: usize,
+    ) -> Result<Self, AttackSequenceError> {
+        if min > max {
+            return Err(AttackSequenceError::UnsupportedLength(
+                Self::MIN_LENGTH,
+                Self::MAX_LENGTH,
+            ));
+        }
+        Self::new(rng.gen_range(min..=max))
     }
 }
 
@@ -85,58 +99,400 @@ const BASE_SEQUENCES: [[Opening; 4]; 4] = [
     ],
 ];
 
+/// The allowed strikes in a fixed order, so the same seed always yields the same sequence.
+pub fn sorted_strikes(
+    allowed_strikes: &HashSet<Strike>,
+) -> Result<Vec<Strike>, AttackSequenceError> {
+    match allowed_strikes.len() {
+        0 => return Err(AttackSequenceError::NoStrikes),
+        1 if allowed_strikes.contains(&Strike::Fehler) => {
+            return Err(AttackSequenceError::OnlyFehlerNotAllowed)
+        }
+        _ => (),
+    }
+
+    let mut allowed_strikes = allowed_strikes
+        .iter()
+        .map(|s| s.to_owned())
+        .collect::<Vec<_>>();
+    allowed_strikes.sort();
+    Ok(allowed_strikes)
+}
+
 impl GenerateAttackSequence for AttackSequenceGenerator {
-    fn gen_random_attack_sequence(
+    fn gen_random_attack_sequence<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
         length: AttackSequenceLength,
         allowed_strikes: &HashSet<Strike>,
         doppelfehler_enabled: bool,
     ) -> Result<AttackSequence, AttackSequenceError> {
-        match allowed_strikes.len() {
-            0 => return Err(AttackSequenceError::NoStrikes),
-            1 => {
-                if allowed_strikes.contains(&Strike::Fehler) {
-                    return Err(AttackSequenceError::OnlyFehlerNotAllowed);
-                }
-            }
-            _ => (),
-        }
+        let allowed_strikes = sorted_strikes(allowed_strikes)?;
+        let sequence_type = BASE_SEQUENCES.choose(rng).unwrap_or(&BASE_SEQUENCES[0]);
+        Ok(build_sequence(
+            length.into(),
+            &allowed_strikes,
+            doppelfehler_enabled,
+            |n, _, strikes| {
+                Attack::new(
+                    sequence_type[n % sequence_type.len()],
+                    *strikes.choose(rng).unwrap(),
+                )
+            },
+        ))
+    }
+}
 
-        let allowed_strikes = allowed_strikes
-            .iter()
-            .map(|s| s.to_owned())
-            .collect::<Vec<_>>();
-
-        let true_strikes = allowed_strikes
-            .iter()
-            .filter(|&s| *s != Strike::Fehler)
-            .cloned()
-            .collect::<Vec<Strike>>();
-
-        let strikes = |fehler_allowed| {
-            if fehler_allowed {
-                &allowed_strikes
-            } else {
-                &true_strikes
-            }
-        };
+impl AttackSequenceGenerator {
+    /// Like the random generator, but picks base sequences and strikes in proportion to the
+    /// weight of the transitions they lead to, e.g. to repeat weak combinations more often.
+    pub fn gen_weighted_attack_sequence<R: Rng + ?Sized>(
+        rng: &mut R,
+        length: AttackSequenceLength,
+        allowed_strikes: &HashSet<Strike>,
+        doppelfehler_enabled: bool,
+        weight: &dyn Fn(Transition) -> f64,
+    ) -> Result<AttackSequence, AttackSequenceError> {
+        let allowed_strikes = sorted_strikes(allowed_strikes)?;
+        let length: usize = length.into();
 
-        let mut rnd = rand::thread_rng();
+        // Base sequences weigh as much as their transitions do on average over the strikes.
         let sequence_type = BASE_SEQUENCES
-            .choose(&mut rnd)
+            .choose_weighted(rng, |openings| {
+                (1..length)
+                    .map(|n| {
+                        let from = openings[(n - 1) % openings.len()];
+                        let to = openings[n % openings.len()];
+                        allowed_strikes
+                            .iter()
+                            .map(|&strike| weight(Transition { from, to, strike }))
+                            .sum::<f64>()
+                            / allowed_strikes.len() as f64
+                    })
+                    .sum::<f64>()
+                    .max(f64::MIN_POSITIVE)
+            })
             .unwrap_or(&BASE_SEQUENCES[0]);
-        let mut sequence: Vec<Attack> = vec![];
 
-        let length: usize = length.into();
-        for n in 0..length {
-            let last_attack = sequence.last();
-            let last_was_fehler = last_attack.is_some_and(|a| a.strike == Strike::Fehler);
-            let fehler_allowed = n != length - 1 && (!last_was_fehler || doppelfehler_enabled);
-
-            sequence.push(Attack {
-                opening: sequence_type[n % sequence_type.len()],
-                strike: *strikes(fehler_allowed).choose(&mut rnd).unwrap(),
+        Ok(build_sequence(
+            length,
+            &allowed_strikes,
+            doppelfehler_enabled,
+            |n, previous, strikes| {
+                let to = sequence_type[n % sequence_type.len()];
+                let strike = match previous {
+                    Some(&Attack { opening: from, .. }) => *strikes
+                        .choose_weighted(rng, |&strike| weight(Transition { from, to, strike }))
+                        .unwrap_or(&strikes[0]),
+                    None => *strikes.choose(rng).unwrap(),
+                };
+                Attack::new(to, strike)
+            },
+        ))
+    }
+}
+
+/// Lets `next` choose each attack, given its index, the previous attack and the strikes the
+/// Fehler rules allow at that point.
+pub fn build_sequence(
+    length: usize,
+    allowed_strikes: &[Strike],
+    doppelfehler_enabled: bool,
+    mut next: impl FnMut(usize, Option<&Attack>, &[Strike]) -> Attack,
+) -> AttackSequence {
+    let true_strikes = allowed_strikes
+        .iter()
+        .filter(|&s| *s != Strike::Fehler)
+        .cloned()
+        .collect::<Vec<Strike>>();
+
+    let strikes = |fehler_allowed| {
+        if fehler_allowed {
+            allowed_strikes
+        } else {
+            &true_strikes
+        }
+    };
+
+    let mut sequence: Vec<Attack> = vec![];
+    for n in 0..length {
+        let last_attack = sequence.last();
+        let last_was_fehler = last_attack.is_some_and(|a| a.strike == Strike::Fehler);
+        let fehler_allowed = n != length - 1 && (!last_was_fehler || doppelfehler_enabled);
+
+        let attack = next(n, last_attack, strikes(fehler_allowed));
+        sequence.push(attack);
+    }
+    sequence
+}
+
+/// Number of valid sequences of the given length: every base sequence combined with every
+/// strike choice that does not end on a Fehler nor, unless enabled, repeats one.
+pub fn count_attack_sequences(
+    length: AttackSequenceLength,
+    allowed_strikes: &HashSet<Strike>,
+    doppelfehler_enabled: bool,
+) -> Result<usize, AttackSequenceError> {
+    let strikes = sorted_strikes(allowed_strikes)?;
+    Ok(count_strike_choices(length.into(), &strikes, doppelfehler_enabled) * BASE_SEQUENCES.len())
+}
+
+fn count_strike_choices(length: usize, strikes: &[Strike], doppelfehler_enabled: bool) -> usize {
+    let fehler = usize::from(strikes.contains(&Strike::Fehler));
+    let true_strikes = strikes.len() - fehler;
+    // Choices ending on a true strike and on a Fehler.
+    let (mut ending_true, mut ending_fehler) = (true_strikes, fehler);
+    for _ in 1..length {
+        let after_fehler = if doppelfehler_enabled {
+            ending_fehler
+        } else {
+            0
+        };
+        (ending_true, ending_fehler) = (
+            (ending_true + ending_fehler) * true_strikes,
+            (ending_true + after_fehler) * fehler,
+        );
+    }
+    ending_true
+}
+
+/// Every valid attack sequence with a length in the range, so a curriculum can walk all
+/// combinations instead of drawing random ones. Ordered by length, base sequence and strikes.
+#[derive(Clone, Debug)]
+pub struct AttackSequences {
+    strikes: Vec<Strike>,
+    doppelfehler_enabled: bool,
+    max_length: usize,
+    length: usize,
+    base_sequence: usize,
+    /// Index into `strikes` for every attack, counted up like an odometer.
+    choices: Vec<usize>,
+    remaining: usize,
+}
+
+impl AttackSequences {
+    pub fn new(
+        min_length: usize,
+        max_length: usize,
+        allowed_strikes: &HashSet<Strike>,
+        doppelfehler_enabled: bool,
+    ) -> Result<Self, AttackSequenceError> {
+        let strikes = sorted_strikes(allowed_strikes)?;
+        let min_length = usize::from(AttackSequenceLength::new(min_length)?);
+        let max_length = usize::from(AttackSequenceLength::new(max_length)?);
+        if min_length > max_length {
+            return Err(AttackSequenceError::UnsupportedLength(
+                AttackSequenceLength::MIN_LENGTH,
+                AttackSequenceLength::MAX_LENGTH,
+            ));
+        }
+
+        let remaining = (min_length..=max_length)
+            .map(|length| {
+                count_attack_sequences(
+                    AttackSequenceLength::new(length)?,
+                    allowed_strikes,
+                    doppelfehler_enabled,
+                )
+            })
+            .sum::<Result<_, _>>()?;
+        Ok(AttackSequences {
+            strikes,
+            doppelfehler_enabled,
+            max_length,
+            length: min_length,
+            base_sequence: 0,
+            choices: vec![0; min_length],
+            remaining,
+        })
+    }
+
+    fn is_valid(&self) -> bool {
+        let is_fehler = |&choice: &usize| self.strikes[choice] == Strike::Fehler;
+        !self.choices.last().is_some_and(is_fehler)
+            && (self.doppelfehler_enabled
+                || !self
+                    .choices
+                    .windows(2)
+                    .any(|pair| pair.iter().all(is_fehler)))
+    }
+
+    /// Moves to the next strike choice, then to the next base sequence and length.
+    fn advance(&mut self) {
+        for choice in self.choices.iter_mut().rev() {
+            *choice += 1;
+            if *choice < self.strikes.len() {
+                return;
+            }
+            *choice = 0;
+        }
+        self.base_sequence += 1;
+        if self.base_sequence == BASE_SEQUENCES.len() {
+            self.base_sequence = 0;
+            self.length += 1;
+            self.choices = vec![0; self.length];
+        }
+    }
+}
+
+impl Iterator for AttackSequences {
+    type Item = AttackSequence;
+
+    fn next(&mut self) -> Option<AttackSequence> {
+        while self.remaining > 0 && self.length <= self.max_length {
+            let sequence = self.is_valid().then(|| {
+                let openings = &BASE_SEQUENCES[self.base_sequence];
+                self.choices
+                    .iter()
+                    .enumerate()
+                    .map(|(n, &choice)| {
+                        Attack::new(openings[n % openings.len()], self.strikes[choice])
+                    })
+                    .collect()
             });
+            self.advance();
+            if sequence.is_some() {
+                self.remaining -= 1;
+                return sequence;
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for AttackSequences {}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{prelude::*, sample::subsequence};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn strike_sets() -> impl Strategy<Value = HashSet<Strike>> {
+        subsequence(Strike::ALL.to_vec(), 0..=Strike::ALL.len())
+            .prop_map(|strikes| strikes.into_iter().collect())
+    }
+
+    fn generate(
+        seed: u64,
+        length: usize,
+        strikes: &HashSet<Strike>,
+        doppelfehler_enabled: bool,
+    ) -> Result<AttackSequence, AttackSequenceError> {
+        AttackSequenceGenerator.gen_random_attack_sequence(
+            &mut StdRng::seed_from_u64(seed),
+            AttackSequenceLength::new(length)?,
+            strikes,
+            doppelfehler_enabled,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn sequences_follow_the_rules(
+            seed in any::<u64>(),
+            length in AttackSequenceLength::MIN_LENGTH..=AttackSequenceLength::MAX_LENGTH,
+            strikes in strike_sets(),
+            doppelfehler_enabled in any::<bool>(),
+        ) {
+            let result = generate(seed, length, &strikes, doppelfehler_enabled);
+            if strikes.is_empty() {
+                prop_assert!(matches!(result, Err(AttackSequenceError::NoStrikes)));
+                return Ok(());
+            }
+            if strikes == HashSet::from([Strike::Fehler]) {
+                prop_assert!(matches!(result, Err(AttackSequenceError::OnlyFehlerNotAllowed)));
+                return Ok(());
+            }
+
+            let sequence = result.unwrap();
+            prop_assert_eq!(sequence.len(), length);
+            prop_assert!(sequence.iter().all(|attack| strikes.contains(&attack.strike)));
+            prop_assert_ne!(sequence.last().unwrap().strike, Strike::Fehler);
+            if !doppelfehler_enabled {
+                prop_assert!(!sequence
+                    .windows(2)
+                    .any(|pair| pair.iter().all(|attack| attack.strike == Strike::Fehler)));
+            }
+            prop_assert!(BASE_SEQUENCES.iter().any(|base| sequence
+                .iter()
+                .enumerate()
+                .all(|(n, attack)| attack.opening == base[n % base.len()])));
+        }
+
+        #[test]
+        fn enumeration_yields_every_valid_sequence_once(
+            length in AttackSequenceLength::MIN_LENGTH..=6usize,
+            strikes in strike_sets(),
+            doppelfehler_enabled in any::<bool>(),
+        ) {
+            let Ok(sequences) = AttackSequences::new(length, length, &strikes, doppelfehler_enabled)
+            else {
+                prop_assert!(strikes.len() <= 1);
+                return Ok(());
+            };
+            let count =
+                count_attack_sequences(AttackSequenceLength::new(length)?, &strikes, doppelfehler_enabled)?;
+            prop_assert_eq!(sequences.len(), count);
+
+            let sequences = sequences.collect::<Vec<_>>();
+            prop_assert_eq!(sequences.len(), count);
+            let distinct = sequences
+                .iter()
+                .map(|s| format!("{s:?}"))
+                .collect::<HashSet<_>>();
+            prop_assert_eq!(distinct.len(), count);
+
+            // Every random sequence is among the enumerated.
+            for seed in 0..20 {
+                let sequence = generate(seed, length, &strikes, doppelfehler_enabled).unwrap();
+                prop_assert!(sequences.contains(&sequence));
+            }
+        }
+
+        #[test]
+        fn lengths_outside_the_range_are_rejected(length in 0..20usize) {
+            let supported =
+                (AttackSequenceLength::MIN_LENGTH..=AttackSequenceLength::MAX_LENGTH)
+                    .contains(&length);
+            match AttackSequenceLength::new(length) {
+                Ok(valid) => prop_assert!(supported && usize::from(valid) == length),
+                Err(AttackSequenceError::UnsupportedLength(min, max)) => {
+                    prop_assert!(!supported);
+                    prop_assert_eq!(
+                        (min, max),
+                        (AttackSequenceLength::MIN_LENGTH, AttackSequenceLength::MAX_LENGTH)
+                    );
+                }
+                Err(e) => prop_assert!(false, "unexpected error {e}"),
+            }
+        }
+
+        #[test]
+        fn ranged_lengths_stay_within_the_range(
+            seed in any::<u64>(),
+            min in 0..12usize,
+            max in 0..12usize,
+        ) {
+            let result = AttackSequenceLength::randomized_range(
+                &mut StdRng::seed_from_u64(seed),
+                min,
+                max,
+            );
+            let supported = AttackSequenceLength::MIN_LENGTH..=AttackSequenceLength::MAX_LENGTH;
+            if min > max {
+                prop_assert!(matches!(result, Err(AttackSequenceError::UnsupportedLength(..))));
+            } else if supported.contains(&min) && supported.contains(&max) {
+                let length = usize::from(result.unwrap());
+                prop_assert!((min..=max).contains(&length));
+            } else if let Ok(length) = result {
+                prop_assert!(supported.contains(&usize::from(length)));
+            }
         }
-        Ok(sequence)
     }
 }
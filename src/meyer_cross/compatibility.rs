@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+
+use super::{
+    attack_sequence::AttackSequence,
+    preparation::Preparation,
+    types::{Attack, Guard, Opening, Strike},
+};
+
+/// The openings and strikes a right-handed fencer can plausibly open the sequence with from a
+/// guard, without first changing sides.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GuardStart {
+    pub openings: &'static [Opening],
+    pub strikes: &'static [Strike],
+}
+
+impl Guard {
+    pub fn start(self) -> GuardStart {
+        use Opening::*;
+        use Strike::*;
+        let (openings, strikes): (&[Opening], &[Strike]) = match self {
+            Guard::TagLeft => (&[TopLeft], &[Long, Flat, Fehler]),
+            Guard::TagRight => (&[TopRight], &[Long, Flat, Fehler]),
+            Guard::TagAbove => (&[TopLeft, TopRight], &[Long, Flat, Fehler]),
+            Guard::PflugLeft => (&[BottomLeft, TopLeft], &[Long, Short, Fehler]),
+            Guard::PflugRight => (&[BottomRight, TopRight], &[Long, Short, Fehler]),
+            Guard::OchsLeft => (&[TopLeft, TopRight], &[Long, Short]),
+            Guard::OchsRight => (&[TopRight, TopLeft], &[Long, Short]),
+            Guard::Eisenport => (&[BottomLeft, BottomRight], &[Long, Short]),
+            Guard::Langort => (&[TopLeft, TopRight, BottomLeft, BottomRight], &Strike::ALL),
+            Guard::Alber => (&[BottomLeft, BottomRight], &[Short, Long, Fehler]),
+        };
+        GuardStart { openings, strikes }
+    }
+}
+
+impl GuardStart {
+    /// Whether the attack opens plausibly. The strike is not checked if the rules allow none of
+    /// the plausible ones.
+    pub fn fits(&self, attack: &Attack, allowed_strikes: &HashSet<Strike>) -> bool {
+        let any_strike_allowed = self.strikes.iter().any(|s| allowed_strikes.contains(s));
+        self.openings.contains(&attack.opening)
+            && (!any_strike_allowed || self.strikes.contains(&attack.strike))
+    }
+}
+
+/// Whether the sequence starts plausibly from the preparation, Streichen lead into any attack.
+pub fn starts_consistently(
+    preparation: Option<Preparation>,
+    attack_sequence: &AttackSequence,
+    allowed_strikes: &HashSet<Strike>,
+) -> bool {
+    match (preparation, attack_sequence.first()) {
+        (Some(Preparation::Static(guard)), Some(first)) => {
+            guard.start().fits(first, allowed_strikes)
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_opening_and_strike_has_a_guard() {
+        for opening in Opening::ALL {
+            assert!(Guard::ALL
+                .iter()
+                .any(|guard| guard.start().openings.contains(&opening)));
+        }
+        for strike in Strike::ALL {
+            assert!(Guard::ALL
+                .iter()
+                .any(|guard| guard.start().strikes.contains(&strike)));
+        }
+    }
+
+    #[test]
+    fn strikes_outside_the_rules_are_not_required() {
+        let attack = Attack::new(Opening::TopRight, Strike::Short);
+        let start = Guard::TagRight.start();
+        assert!(!start.fits(&attack, &HashSet::from([Strike::Long, Strike::Short])));
+        assert!(start.fits(&attack, &HashSet::from([Strike::Short])));
+    }
+}
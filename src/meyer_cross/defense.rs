@@ -0,0 +1,82 @@
+use std::{collections::HashSet, fmt};
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::attack_sequence::AttackSequence;
+
+/// How the partner answers an attack in the two-role drills.
+#[derive(Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Hash, Debug)]
+pub enum Defense {
+    Parry,
+    /// Displacing the attack with a strike of one's own.
+    Versetzen,
+    CounterCut,
+}
+
+impl Defense {
+    pub const ALL: [Defense; 3] = [Defense::Parry, Defense::Versetzen, Defense::CounterCut];
+
+    pub fn letter(self) -> &'static str {
+        match self {
+            Defense::Parry => "P",
+            Defense::Versetzen => "V",
+            Defense::CounterCut => "C",
+        }
+    }
+
+    /// Picks the expected response to every attack at random among the allowed defenses.
+    /// Without allowed defenses, the attacks get none.
+    pub fn respond<R: Rng + ?Sized>(
+        rng: &mut R,
+        attack_sequence: &mut AttackSequence,
+        allowed_defenses: &HashSet<Defense>,
+    ) {
+        let mut defenses = allowed_defenses.iter().copied().collect::<Vec<_>>();
+        defenses.sort();
+        for attack in attack_sequence {
+            attack.response = defenses.choose(rng).copied();
+        }
+    }
+}
+
+impl fmt::Display for Defense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Defense::Parry => "Parry",
+            Defense::Versetzen => "Versetzen",
+            Defense::CounterCut => "Counter-Cut",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::meyer_cross::{rules::DrillRules, MeyerCross};
+
+    #[test]
+    fn responses_come_from_the_allowed_defenses() {
+        let mut rng = StdRng::seed_from_u64(50);
+        let mut rules = DrillRules {
+            min_length: 8,
+            max_length: 8,
+            ..DrillRules::default()
+        };
+        let alone = MeyerCross::random(&mut rng, &rules).unwrap();
+        assert!(alone
+            .attack_sequence
+            .unwrap()
+            .iter()
+            .all(|a| a.response.is_none()));
+
+        rules.defenses = HashSet::from([Defense::Parry, Defense::CounterCut]);
+        for _ in 0..20 {
+            let drill = MeyerCross::random(&mut rng, &rules).unwrap();
+            for attack in drill.attack_sequence.unwrap() {
+                assert!(rules.defenses.contains(&attack.response.unwrap()));
+            }
+        }
+    }
+}
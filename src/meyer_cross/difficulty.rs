@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+
+use super::{
+    preparation::{Preparation, StreichenCount},
+    rules::DrillRules,
+    types::Strike,
+    MeyerCross,
+};
+
+/// What makes a drill hard, each part already weighted so they add up to the score.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct DifficultyScore {
+    pub length: f32,
+    pub strike_types: f32,
+    pub fehler: f32,
+    pub doppelfehler: f32,
+    pub preparation: f32,
+}
+
+impl DifficultyScore {
+    const PER_ATTACK: f32 = 1.0;
+    const PER_EXTRA_STRIKE_TYPE: f32 = 1.5;
+    const PER_FEHLER: f32 = 1.0;
+    const PER_DOPPELFEHLER: f32 = 2.0;
+
+    pub fn of(drill: &MeyerCross) -> Self {
+        let attacks = drill.attack_sequence.as_deref().unwrap_or_default();
+        let strike_types = attacks
+            .iter()
+            .map(|attack| attack.strike)
+            .collect::<HashSet<_>>()
+            .len();
+        let is_fehler = |strike| strike == Strike::Fehler;
+        DifficultyScore {
+            length: attacks.len() as f32 * Self::PER_ATTACK,
+            strike_types: strike_types.saturating_sub(1) as f32 * Self::PER_EXTRA_STRIKE_TYPE,
+            fehler: attacks.iter().filter(|a| is_fehler(a.strike)).count() as f32
+                * Self::PER_FEHLER,
+            doppelfehler: attacks
+                .windows(2)
+                .filter(|pair| pair.iter().all(|a| is_fehler(a.strike)))
+                .count() as f32
+                * Self::PER_DOPPELFEHLER,
+            preparation: drill.preparation.map_or(0.0, preparation_complexity),
+        }
+    }
+
+    pub fn total(&self) -> f32 {
+        self.length + self.strike_types + self.fehler + self.doppelfehler + self.preparation
+    }
+}
+
+/// Three Streichen are the usual start, other counts and guards break the habit.
+fn preparation_complexity(preparation: Preparation) -> f32 {
+    match preparation {
+        Preparation::Streichen(count) if count == StreichenCount::default() => 0.0,
+        Preparation::Streichen(_) => 0.5,
+        Preparation::Static(_) => 1.0,
+    }
+}
+
+/// Strikes, length range and Doppelfehler of each difficulty level, easiest first.
+const LEVELS: [(&[Strike], usize, usize, bool); 8] = [
+    (&[Strike::Long], 2, 3, false),
+    (&[Strike::Long], 4, 4, false),
+    (&[Strike::Long, Strike::Short], 4, 4, false),
+    (&[Strike::Long, Strike::Short], 4, 6, false),
+    (&[Strike::Long, Strike::Short, Strike::Flat], 4, 6, false),
+    (&Strike::ALL, 4, 6, false),
+    (&Strike::ALL, 6, 8, false),
+    (&Strike::ALL, 6, 8, true),
+];
+
+/// Moves the fencer up a level after good drills and down after poor ones.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Progression {
+    level: usize,
+    outcomes: Vec<f32>,
+}
+
+impl Default for Progression {
+    fn default() -> Self {
+        Progression {
+            level: 1,
+            outcomes: vec![],
+        }
+    }
+}
+
+impl Progression {
+    pub const LEVELS: usize = LEVELS.len();
+    /// Drills judged together before the level changes.
+    pub const DRILLS_PER_STEP: usize = 5;
+    const RAISE_AT: f32 = 0.8;
+    const LOWER_BELOW: f32 = 0.5;
+
+    /// The current level, 0 being the easiest.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Records how well a drill went, from 0 for failed to 1 for perfect, and returns whether
+    /// the level changed.
+    pub fn record(&mut self, outcome: f32) -> bool {
+        self.outcomes.push(outcome.clamp(0.0, 1.0));
+        if self.outcomes.len() < Self::DRILLS_PER_STEP {
+            return false;
+        }
+
+        let average = self.outcomes.iter().sum::<f32>() / self.outcomes.len() as f32;
+        self.outcomes.clear();
+        let level = if average >= Self::RAISE_AT {
+            (self.level + 1).min(Self::LEVELS - 1)
+        } else if average < Self::LOWER_BELOW {
+            self.level.saturating_sub(1)
+        } else {
+            self.level
+        };
+        std::mem::replace(&mut self.level, level) != level
+    }
+
+    /// The rules with strikes, length and Doppelfehler of the current level.
+    pub fn apply_to(&self, rules: &mut DrillRules) {
+        let (strikes, min_length, max_length, doppelfehler_enabled) = LEVELS[self.level];
+        rules.strikes = strikes.iter().copied().collect();
+        rules.min_length = min_length;
+        rules.max_length = max_length;
+        rules.doppelfehler_enabled = doppelfehler_enabled;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meyer_cross::types::{Attack, Guard, Opening};
+
+    fn drill(preparation: Preparation, strikes: &[Strike]) -> MeyerCross {
+        let mut drill = MeyerCross::new();
+        drill.preparation = Some(preparation);
+        drill.attack_sequence = Some(
+            strikes
+                .iter()
+                .map(|&strike| Attack::new(Opening::TopLeft, strike))
+                .collect(),
+        );
+        drill
+    }
+
+    #[test]
+    fn harder_drills_score_higher() {
+        use Strike::*;
+        let streichen = Preparation::Streichen(StreichenCount::default());
+        let easy = DifficultyScore::of(&drill(streichen, &[Long, Long, Long, Long]));
+        assert_eq!(easy.total(), 4.0);
+
+        let harder = [
+            drill(streichen, &[Long, Long, Long, Long, Long]),
+            drill(streichen, &[Long, Short, Long, Long]),
+            drill(streichen, &[Long, Fehler, Long, Long]),
+            drill(Preparation::Static(Guard::Alber), &[Long, Long, Long, Long]),
+        ];
+        for drill in harder {
+            assert!(
+                DifficultyScore::of(&drill).total() > easy.total(),
+                "{drill:?}"
+            );
+        }
+
+        let fehler = DifficultyScore::of(&drill(streichen, &[Long, Fehler, Long, Long]));
+        let doppelfehler = DifficultyScore::of(&drill(streichen, &[Fehler, Fehler, Long, Long]));
+        assert!(doppelfehler.total() > fehler.total());
+    }
+
+    #[test]
+    fn good_drills_raise_and_poor_drills_lower_the_level() {
+        let mut progression = Progression::default();
+        let start = progression.level();
+
+        let changes = (0..Progression::DRILLS_PER_STEP)
+            .map(|_| progression.record(1.0))
+            .collect::<Vec<_>>();
+        assert_eq!(changes.iter().filter(|&&c| c).count(), 1);
+        assert_eq!(progression.level(), start + 1);
+
+        for _ in 0..Progression::DRILLS_PER_STEP {
+            progression.record(0.6);
+        }
+        assert_eq!(progression.level(), start + 1);
+
+        for _ in 0..Progression::DRILLS_PER_STEP * 5 {
+            progression.record(0.0);
+        }
+        assert_eq!(progression.level(), 0);
+
+        for _ in 0..Progression::DRILLS_PER_STEP * 20 {
+            progression.record(1.0);
+        }
+        assert_eq!(progression.level(), Progression::LEVELS - 1);
+    }
+
+    #[test]
+    fn levels_make_valid_rules() {
+        let mut progression = Progression::default();
+        let mut rng = rand::thread_rng();
+        for _ in 0..Progression::LEVELS {
+            let mut rules = DrillRules::default();
+            progression.apply_to(&mut rules);
+            assert!(MeyerCross::random(&mut rng, &rules).is_ok());
+            for _ in 0..Progression::DRILLS_PER_STEP {
+                progression.record(1.0);
+            }
+        }
+    }
+}
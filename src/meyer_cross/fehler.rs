@@ -0,0 +1,78 @@
+use std::fmt;
+
+use super::{
+    attack_sequence::AttackSequence,
+    types::{Opening, Strike},
+};
+
+/// Where a Fehler really strikes after feinting to its opening.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum FehlerTarget {
+    /// Feint high and strike low on the same side, or the other way round.
+    #[default]
+    HighLow,
+    /// Feint to one side and strike the other side at the same height.
+    OtherSide,
+    /// Strike the opening diagonally across from the feint.
+    Diagonal,
+}
+
+impl FehlerTarget {
+    pub const ALL: [FehlerTarget; 3] = [
+        FehlerTarget::HighLow,
+        FehlerTarget::OtherSide,
+        FehlerTarget::Diagonal,
+    ];
+
+    pub fn target(self, feint: Opening) -> Opening {
+        use Opening::*;
+        match (self, feint) {
+            (FehlerTarget::HighLow, TopLeft) | (FehlerTarget::OtherSide, BottomRight) => BottomLeft,
+            (FehlerTarget::HighLow, TopRight) | (FehlerTarget::OtherSide, BottomLeft) => {
+                BottomRight
+            }
+            (FehlerTarget::HighLow, BottomLeft) | (FehlerTarget::OtherSide, TopRight) => TopLeft,
+            (FehlerTarget::HighLow, BottomRight) | (FehlerTarget::OtherSide, TopLeft) => TopRight,
+            (FehlerTarget::Diagonal, TopLeft) => BottomRight,
+            (FehlerTarget::Diagonal, TopRight) => BottomLeft,
+            (FehlerTarget::Diagonal, BottomLeft) => TopRight,
+            (FehlerTarget::Diagonal, BottomRight) => TopLeft,
+        }
+    }
+
+    /// Gives every Fehler of the sequence its real target, the other attacks have none.
+    pub fn apply(self, attack_sequence: &mut AttackSequence) {
+        for attack in attack_sequence {
+            attack.target = (attack.strike == Strike::Fehler).then(|| self.target(attack.opening));
+        }
+    }
+}
+
+impl fmt::Display for FehlerTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FehlerTarget::HighLow => "High to Low",
+            FehlerTarget::OtherSide => "Other Side",
+            FehlerTarget::Diagonal => "Diagonal",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meyer_cross::mirror::Mirror;
+
+    #[test]
+    fn targets_differ_from_the_feint_and_survive_mirroring() {
+        for rule in FehlerTarget::ALL {
+            for feint in Opening::ALL {
+                let target = rule.target(feint);
+                assert_ne!(target, feint, "{rule} from {feint}");
+                assert_eq!(rule.target(feint.mirrored()), target.mirrored());
+            }
+        }
+        let high = FehlerTarget::HighLow.target(Opening::TopRight);
+        assert_eq!(high, Opening::BottomRight);
+    }
+}
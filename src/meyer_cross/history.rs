@@ -0,0 +1,112 @@
+use std::{collections::VecDeque, fmt};
+
+use super::{attack_sequence::AttackSequence, preparation::Preparation};
+
+/// Which of the recent drills a new drill must not repeat.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum RepeatAvoidance {
+    #[default]
+    Off,
+    /// The same attack sequence, whatever the partner responds with.
+    Exact,
+    /// The same openings in the same order, whatever the strikes.
+    SamePattern,
+}
+
+impl RepeatAvoidance {
+    pub const ALL: [RepeatAvoidance; 3] = [
+        RepeatAvoidance::Off,
+        RepeatAvoidance::Exact,
+        RepeatAvoidance::SamePattern,
+    ];
+
+    pub fn repeats(&self, a: &AttackSequence, b: &AttackSequence) -> bool {
+        match self {
+            RepeatAvoidance::Off => false,
+            RepeatAvoidance::Exact => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b)
+                        .all(|(a, b)| a.opening == b.opening && a.strike == b.strike)
+            }
+            RepeatAvoidance::SamePattern => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.opening == b.opening)
+            }
+        }
+    }
+}
+
+impl fmt::Display for RepeatAvoidance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RepeatAvoidance::Off => "Off",
+            RepeatAvoidance::Exact => "Same Sequence",
+            RepeatAvoidance::SamePattern => "Same Openings",
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PastDrill {
+    pub preparation: Option<Preparation>,
+    pub attack_sequence: AttackSequence,
+}
+
+/// The most recent drills, newest first.
+#[derive(Clone, Default, Debug)]
+pub struct DrillHistory {
+    drills: VecDeque<PastDrill>,
+}
+
+impl DrillHistory {
+    pub const CAPACITY: usize = 20;
+
+    pub fn push(&mut self, drill: PastDrill) {
+        self.drills.push_front(drill);
+        self.drills.truncate(Self::CAPACITY);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PastDrill> {
+        self.drills.iter()
+    }
+
+    /// Whether the sequence repeats one of the last `window` drills.
+    pub fn contains(
+        &self,
+        attack_sequence: &AttackSequence,
+        avoidance: RepeatAvoidance,
+        window: usize,
+    ) -> bool {
+        self.drills
+            .iter()
+            .take(window)
+            .any(|drill| avoidance.repeats(&drill.attack_sequence, attack_sequence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meyer_cross::{
+        defense::Defense,
+        types::{Attack, Opening, Strike},
+    };
+
+    #[test]
+    fn partner_responses_do_not_make_a_sequence_new() {
+        let sequence = |response| {
+            vec![
+                Attack {
+                    response: Some(response),
+                    ..Attack::new(Opening::TopLeft, Strike::Long)
+                };
+                4
+            ]
+        };
+        let (parried, countered) = (sequence(Defense::Parry), sequence(Defense::CounterCut));
+        assert!(RepeatAvoidance::Exact.repeats(&parried, &countered));
+        let mut shorter = countered.clone();
+        shorter.pop();
+        assert!(!RepeatAvoidance::Exact.repeats(&parried, &shorter));
+    }
+}
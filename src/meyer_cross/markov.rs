@@ -0,0 +1,249 @@
+use std::{collections::HashSet, fs, path::Path};
+
+use rand::{seq::SliceRandom, Rng};
+use thiserror::Error;
+
+use super::{
+    attack_sequence::{
+        build_sequence, sorted_strikes, AttackSequence, AttackSequenceError, AttackSequenceLength,
+        GenerateAttackSequence,
+    },
+    types::{parse_name, Attack, Opening, Strike, Transition},
+};
+
+#[derive(Error, Debug)]
+pub enum MarkovError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Not a transition matrix.")]
+    UnknownFormat,
+    #[error("Invalid matrix row in line {0}.")]
+    InvalidLine(usize),
+}
+
+/// Weights of moving from a row's to a column's opening or strike, in the order of `ALL`.
+pub type Weights = [[f64; 4]; 4];
+
+/// Generates sequences as a Markov chain: each opening and strike is drawn depending on the
+/// previous attack, weighted by the transition matrices, instead of following a base sequence.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MarkovGenerator {
+    pub openings: Weights,
+    pub strikes: Weights,
+}
+
+impl Default for MarkovGenerator {
+    /// Moves along the diagonals and the horizontals of the cross like the base sequences do,
+    /// favoring Long and Short edge on the same line and avoiding Flat after Fehler.
+    fn default() -> Self {
+        MarkovGenerator {
+            openings: [
+                [0.0, 1.0, 0.0, 1.0],
+                [1.0, 0.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0, 1.0],
+                [1.0, 0.0, 1.0, 0.0],
+            ],
+            strikes: [
+                [1.0, 2.0, 1.0, 1.0],
+                [2.0, 1.0, 1.0, 1.0],
+                [1.0, 1.0, 1.0, 1.0],
+                [2.0, 2.0, 0.5, 1.0],
+            ],
+        }
+    }
+}
+
+const FORMAT: &str = "meyer-cross-markov/1";
+
+fn index<T: PartialEq>(all: &[T], value: T) -> usize {
+    all.iter().position(|v| *v == value).unwrap_or_default()
+}
+
+impl MarkovGenerator {
+    pub fn opening_weight(&self, from: Opening, to: Opening) -> f64 {
+        self.openings[index(&Opening::ALL, from)][index(&Opening::ALL, to)]
+    }
+
+    pub fn strike_weight(&self, from: Strike, to: Strike) -> f64 {
+        self.strikes[index(&Strike::ALL, from)][index(&Strike::ALL, to)]
+    }
+
+    /// Like [`GenerateAttackSequence::gen_random_attack_sequence`], with the strike weights
+    /// scaled by the weight of the transition, e.g. to repeat weak combinations more often.
+    pub fn gen_weighted_attack_sequence<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        length: AttackSequenceLength,
+        allowed_strikes: &HashSet<Strike>,
+        doppelfehler_enabled: bool,
+        weight: &dyn Fn(Transition) -> f64,
+    ) -> Result<AttackSequence, AttackSequenceError> {
+        let allowed_strikes = sorted_strikes(allowed_strikes)?;
+        Ok(build_sequence(
+            length.into(),
+            &allowed_strikes,
+            doppelfehler_enabled,
+            |_, previous, strikes| {
+                let Some(&previous) = previous else {
+                    return Attack::new(
+                        *Opening::ALL.choose(rng).unwrap(),
+                        *strikes.choose(rng).unwrap(),
+                    );
+                };
+                // Rows without any weight leave the choice to chance rather than fail.
+                let opening = *Opening::ALL
+                    .choose_weighted(rng, |&to| self.opening_weight(previous.opening, to))
+                    .or_else(|_| Opening::ALL.choose(rng).ok_or(()))
+                    .unwrap();
+                let strike = *strikes
+                    .choose_weighted(rng, |&strike| {
+                        self.strike_weight(previous.strike, strike)
+                            * weight(Transition {
+                                from: previous.opening,
+                                to: opening,
+                                strike,
+                            })
+                    })
+                    .or_else(|_| strikes.choose(rng).ok_or(()))
+                    .unwrap();
+                Attack::new(opening, strike)
+            },
+        ))
+    }
+
+    /// One row per line, e.g. `strike Fehler 2 2 0.5 1` for the weights after a Fehler.
+    pub fn encode(&self) -> String {
+        let mut text = format!(
+            "{FORMAT}\n# Columns: {:?}, {:?}\n",
+            Opening::ALL,
+            Strike::ALL
+        );
+        for (opening, row) in Opening::ALL.iter().zip(&self.openings) {
+            text += &format!("opening {opening:?} {}\n", encode_row(row));
+        }
+        for (strike, row) in Strike::ALL.iter().zip(&self.strikes) {
+            text += &format!("strike {strike:?} {}\n", encode_row(row));
+        }
+        text
+    }
+
+    /// Rows missing from the text keep their default weights.
+    pub fn decode(text: &str) -> Result<Self, MarkovError> {
+        let mut generator = Self::default();
+        let mut lines = text.lines();
+        if lines.next() != Some(FORMAT) {
+            return Err(MarkovError::UnknownFormat);
+        }
+        for (index, line) in lines.enumerate() {
+            let invalid = || MarkovError::InvalidLine(index + 2);
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            let (row, weights) = match words[..] {
+                [] => continue,
+                [comment, ..] if comment.starts_with('#') => continue,
+                ["opening", name, ref weights @ ..] => {
+                    let opening = parse_name(&Opening::ALL, name).ok_or_else(invalid)?;
+                    (
+                        &mut generator.openings[self::index(&Opening::ALL, opening)],
+                        weights,
+                    )
+                }
+                ["strike", name, ref weights @ ..] => {
+                    let strike = parse_name(&Strike::ALL, name).ok_or_else(invalid)?;
+                    (
+                        &mut generator.strikes[self::index(&Strike::ALL, strike)],
+                        weights,
+                    )
+                }
+                _ => return Err(invalid()),
+            };
+            if weights.len() != row.len() {
+                return Err(invalid());
+            }
+            for (cell, weight) in row.iter_mut().zip(weights) {
+                *cell = weight
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|w| w.is_finite() && *w >= 0.0)
+                    .ok_or_else(invalid)?;
+            }
+        }
+        Ok(generator)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, MarkovError> {
+        Self::decode(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), MarkovError> {
+        fs::write(path, self.encode())?;
+        Ok(())
+    }
+}
+
+fn encode_row(row: &[f64; 4]) -> String {
+    row.map(|weight| weight.to_string()).join(" ")
+}
+
+impl GenerateAttackSequence for MarkovGenerator {
+    fn gen_random_attack_sequence<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        length: AttackSequenceLength,
+        allowed_strikes: &HashSet<Strike>,
+        doppelfehler_enabled: bool,
+    ) -> Result<AttackSequence, AttackSequenceError> {
+        self.gen_weighted_attack_sequence(
+            rng,
+            length,
+            allowed_strikes,
+            doppelfehler_enabled,
+            &|_| 1.0,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn sequences_follow_the_matrix() {
+        let mut generator = MarkovGenerator::default();
+        // Only Short after Long and only Long after Short.
+        generator.strikes[0] = [0.0, 1.0, 0.0, 0.0];
+        generator.strikes[1] = [1.0, 0.0, 0.0, 0.0];
+        let strikes = HashSet::from([Strike::Long, Strike::Short, Strike::Flat]);
+
+        let mut rng = StdRng::seed_from_u64(8);
+        for _ in 0..100 {
+            let sequence = generator
+                .gen_random_attack_sequence(
+                    &mut rng,
+                    AttackSequenceLength::new(6).unwrap(),
+                    &strikes,
+                    false,
+                )
+                .unwrap();
+            for pair in sequence.windows(2) {
+                assert!(generator.opening_weight(pair[0].opening, pair[1].opening) > 0.0);
+                assert!(generator.strike_weight(pair[0].strike, pair[1].strike) > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn matrices_survive_encoding() {
+        let mut generator = MarkovGenerator::default();
+        generator.openings[2] = [0.25, 0.0, 3.0, 1.5];
+        assert_eq!(
+            MarkovGenerator::decode(&generator.encode()).unwrap(),
+            generator
+        );
+        assert!(matches!(
+            MarkovGenerator::decode(&format!("{FORMAT}\nstrike Long 1 -1 1 1")),
+            Err(MarkovError::InvalidLine(2))
+        ));
+    }
+}
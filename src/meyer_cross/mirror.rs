@@ -0,0 +1,71 @@
+use super::{
+    preparation::Preparation,
+    types::{Attack, Guard, Opening, Transition},
+};
+
+/// Swaps left and right, e.g. for left-handed fencers or the partner's facing view.
+pub trait Mirror: Sized {
+    fn mirrored(self) -> Self;
+
+    fn mirrored_if(self, condition: bool) -> Self {
+        if condition {
+            self.mirrored()
+        } else {
+            self
+        }
+    }
+}
+
+impl Mirror for Opening {
+    fn mirrored(self) -> Self {
+        match self {
+            Opening::TopLeft => Opening::TopRight,
+            Opening::TopRight => Opening::TopLeft,
+            Opening::BottomLeft => Opening::BottomRight,
+            Opening::BottomRight => Opening::BottomLeft,
+        }
+    }
+}
+
+impl Mirror for Guard {
+    fn mirrored(self) -> Self {
+        match self {
+            Guard::TagLeft => Guard::TagRight,
+            Guard::TagRight => Guard::TagLeft,
+            Guard::PflugLeft => Guard::PflugRight,
+            Guard::PflugRight => Guard::PflugLeft,
+            Guard::OchsLeft => Guard::OchsRight,
+            Guard::OchsRight => Guard::OchsLeft,
+            Guard::TagAbove | Guard::Eisenport | Guard::Langort | Guard::Alber => self,
+        }
+    }
+}
+
+impl Mirror for Attack {
+    fn mirrored(self) -> Self {
+        Attack {
+            opening: self.opening.mirrored(),
+            target: self.target.map(Mirror::mirrored),
+            ..self
+        }
+    }
+}
+
+impl Mirror for Preparation {
+    fn mirrored(self) -> Self {
+        match self {
+            Preparation::Static(guard) => Preparation::Static(guard.mirrored()),
+            Preparation::Streichen(_) => self,
+        }
+    }
+}
+
+impl Mirror for Transition {
+    fn mirrored(self) -> Self {
+        Transition {
+            from: self.from.mirrored(),
+            to: self.to.mirrored(),
+            ..self
+        }
+    }
+}
@@ -1,16 +1,33 @@
+pub mod analysis;
 pub mod attack_sequence;
+pub mod compatibility;
+pub mod defense;
+pub mod difficulty;
+pub mod fehler;
+pub mod history;
+pub mod markov;
+pub mod mirror;
 pub mod preparation;
+pub mod repetition;
+pub mod rules;
+pub mod share;
 pub mod types;
 
 use std::collections::HashSet;
 
+use rand::Rng;
 use thiserror::Error;
 
 use attack_sequence::{
     AttackSequenceError, AttackSequenceGenerator, AttackSequenceLength, GenerateAttackSequence,
 };
+use compatibility::starts_consistently;
+use defense::Defense;
+use history::{DrillHistory, PastDrill};
+use mirror::Mirror;
 use preparation::{Preparation, PreparationError, RandomizePreparation};
-use types::Strike;
+use repetition::RepetitionSchedule;
+use rules::DrillRules;
 
 use self::{attack_sequence::AttackSequence, preparation::PreparationRandomizer};
 
@@ -22,10 +39,23 @@ pub enum MeyerCrossError {
     Preparation(#[from] PreparationError),
 }
 
-#[derive(Debug)]
+/// Attempts to find a drill that repeats none of the recent ones, before a repeat is accepted.
+const REPEAT_ATTEMPTS: usize = 50;
+/// Attempts to find a sequence that starts plausibly from the guard, before any is accepted.
+const START_ATTEMPTS: usize = 50;
+
+#[derive(Clone, Debug)]
 pub struct MeyerCross {
     pub preparation: Option<Preparation>,
     pub attack_sequence: Option<AttackSequence>,
+    history: DrillHistory,
+}
+
+/// Drills are equal by preparation and sequence, whatever came before them.
+impl PartialEq for MeyerCross {
+    fn eq(&self, other: &Self) -> bool {
+        self.preparation == other.preparation && self.attack_sequence == other.attack_sequence
+    }
 }
 
 impl MeyerCross {
@@ -33,28 +63,313 @@ impl MeyerCross {
         MeyerCross {
             preparation: None,
             attack_sequence: None,
+            history: DrillHistory::default(),
         }
     }
 
+    /// The drills shown before the current one, newest first.
+    pub fn history(&self) -> &DrillHistory {
+        &self.history
+    }
+
+    /// Shows the drill, the current one moves into the history.
+    pub fn replace(&mut self, drill: MeyerCross) {
+        if let Some(attack_sequence) = self.attack_sequence.take() {
+            self.history.push(PastDrill {
+                preparation: self.preparation,
+                attack_sequence,
+            });
+        }
+        self.preparation = drill.preparation;
+        self.attack_sequence = drill.attack_sequence;
+    }
+
+    /// Whether the drill repeats the current or a recent drill in the way the rules forbid.
+    fn repeats_recent(&self, drill: &MeyerCross, rules: &DrillRules) -> bool {
+        let Some(attack_sequence) = drill.attack_sequence.as_ref() else {
+            return false;
+        };
+        self.attack_sequence.as_ref().is_some_and(|current| {
+            rules.repeat_window > 0 && rules.repeat_avoidance.repeats(current, attack_sequence)
+        }) || self.history.contains(
+            attack_sequence,
+            rules.repeat_avoidance,
+            rules.repeat_window.saturating_sub(1),
+        )
+    }
+
     #[allow(dead_code)]
     pub fn clear(&mut self) {
         self.preparation = None;
         self.attack_sequence = None;
     }
 
-    pub fn randomize(
+    pub fn randomize_with_rules<R: Rng + ?Sized>(
         &mut self,
-        length: AttackSequenceLength,
-        preparations: &HashSet<Preparation>,
-        strikes: &HashSet<Strike>,
-        doppelfehler_enabled: bool,
+        rng: &mut R,
+        rules: &DrillRules,
     ) -> Result<(), MeyerCrossError> {
-        self.preparation = Some(PreparationRandomizer::gen_random_preparation(preparations)?);
-        self.attack_sequence = Some(AttackSequenceGenerator::gen_random_attack_sequence(
-            length,
-            strikes,
-            doppelfehler_enabled,
-        )?);
+        self.randomize_avoiding_repeats(rng, rules, |rng, length| match &rules.markov {
+            Some(markov) => markov.gen_random_attack_sequence(
+                rng,
+                length,
+                &rules.strikes,
+                rules.doppelfehler_enabled,
+            ),
+            None => AttackSequenceGenerator.gen_random_attack_sequence(
+                rng,
+                length,
+                &rules.strikes,
+                rules.doppelfehler_enabled,
+            ),
+        })
+    }
+
+    /// Like [`Self::randomize_with_rules`], but favors the transitions the schedule has due.
+    pub fn randomize_with_schedule<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        rules: &DrillRules,
+        schedule: &RepetitionSchedule,
+    ) -> Result<(), MeyerCrossError> {
+        // The schedule is kept right-handed, drills are mirrored after generating them.
+        let weight = |transition| schedule.weight(transition);
+        self.randomize_avoiding_repeats(rng, rules, |rng, length| match &rules.markov {
+            Some(markov) => markov.gen_weighted_attack_sequence(
+                rng,
+                length,
+                &rules.strikes,
+                rules.doppelfehler_enabled,
+                &weight,
+            ),
+            None => AttackSequenceGenerator::gen_weighted_attack_sequence(
+                rng,
+                length,
+                &rules.strikes,
+                rules.doppelfehler_enabled,
+                &weight,
+            ),
+        })
+    }
+
+    fn randomize_avoiding_repeats<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        rules: &DrillRules,
+        mut attack_sequence: impl FnMut(
+            &mut R,
+            AttackSequenceLength,
+        ) -> Result<AttackSequence, AttackSequenceError>,
+    ) -> Result<(), MeyerCrossError> {
+        let mut drill = Self::new();
+        for _ in 0..REPEAT_ATTEMPTS {
+            let length =
+                AttackSequenceLength::randomized_range(rng, rules.min_length, rules.max_length)?;
+            let preparation =
+                PreparationRandomizer::gen_random_preparation(rng, &rules.preparations)?;
+            let mut sequence = attack_sequence(rng, length)?;
+            if rules.guard_start {
+                for _ in 0..START_ATTEMPTS {
+                    if starts_consistently(Some(preparation), &sequence, &rules.strikes) {
+                        break;
+                    }
+                    sequence = attack_sequence(rng, length)?;
+                }
+            }
+            rules.fehler_target.apply(&mut sequence);
+            Defense::respond(rng, &mut sequence, &rules.defenses);
+            drill.preparation = Some(preparation);
+            drill.attack_sequence = Some(sequence);
+            if rules.left_handed {
+                drill.mirror();
+            }
+            if !self.repeats_recent(&drill, rules) {
+                break;
+            }
+        }
+        self.replace(drill);
         Ok(())
     }
+
+    /// A drill with the given sequence and a random preparation, e.g. while walking through all
+    /// sequences the rules allow.
+    pub fn with_sequence<R: Rng + ?Sized>(
+        rng: &mut R,
+        rules: &DrillRules,
+        mut attack_sequence: AttackSequence,
+    ) -> Result<Self, MeyerCrossError> {
+        rules.fehler_target.apply(&mut attack_sequence);
+        Defense::respond(rng, &mut attack_sequence, &rules.defenses);
+        let fitting = rules
+            .preparations
+            .iter()
+            .copied()
+            .filter(|&p| starts_consistently(Some(p), &attack_sequence, &rules.strikes))
+            .collect::<HashSet<_>>();
+        let preparations = if rules.guard_start && !fitting.is_empty() {
+            &fitting
+        } else {
+            &rules.preparations
+        };
+        let mut meyer_cross = MeyerCross {
+            preparation: Some(PreparationRandomizer::gen_random_preparation(
+                rng,
+                preparations,
+            )?),
+            attack_sequence: Some(attack_sequence),
+            history: DrillHistory::default(),
+        };
+        if rules.left_handed {
+            meyer_cross.mirror();
+        }
+        Ok(meyer_cross)
+    }
+
+    pub fn random<R: Rng + ?Sized>(
+        rng: &mut R,
+        rules: &DrillRules,
+    ) -> Result<Self, MeyerCrossError> {
+        let mut meyer_cross = Self::new();
+        meyer_cross.randomize_with_rules(rng, rules)?;
+        Ok(meyer_cross)
+    }
+
+    /// Mirrors the whole drill, turning a right-handed drill into its left-handed counterpart.
+    pub fn mirror(&mut self) {
+        self.preparation = self.preparation.map(Mirror::mirrored);
+        if let Some(attack_sequence) = self.attack_sequence.as_mut() {
+            attack_sequence
+                .iter_mut()
+                .for_each(|attack| *attack = attack.mirrored());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{
+        history::RepeatAvoidance,
+        repetition::Recall,
+        types::{Guard, Opening, Strike, Transition},
+        *,
+    };
+
+    fn sequences(rules: &DrillRules, drills: usize) -> Vec<AttackSequence> {
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut meyer_cross = MeyerCross::new();
+        (0..drills)
+            .map(|_| {
+                meyer_cross.randomize_with_rules(&mut rng, rules).unwrap();
+                meyer_cross.attack_sequence.clone().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn no_repeats_within_the_window() {
+        // Long edges only leave one sequence per base sequence.
+        let rules = DrillRules {
+            repeat_avoidance: RepeatAvoidance::Exact,
+            repeat_window: 3,
+            ..DrillRules::default()
+        };
+        let sequences = sequences(&rules, 30);
+        for window in sequences.windows(3) {
+            assert!(window[0] != window[1] && window[0] != window[2] && window[1] != window[2]);
+        }
+    }
+
+    #[test]
+    fn no_repeated_openings_within_the_window() {
+        let rules = DrillRules {
+            strikes: HashSet::from([Strike::Long, Strike::Short, Strike::Flat]),
+            repeat_avoidance: RepeatAvoidance::SamePattern,
+            repeat_window: 2,
+            ..DrillRules::default()
+        };
+        let sequences = sequences(&rules, 30);
+        for pair in sequences.windows(2) {
+            assert!(!RepeatAvoidance::SamePattern.repeats(&pair[0], &pair[1]));
+        }
+    }
+
+    #[test]
+    fn history_is_bounded_and_newest_first() {
+        let rules = DrillRules::default();
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut meyer_cross = MeyerCross::new();
+        let mut previous = None;
+        for _ in 0..history::DrillHistory::CAPACITY + 5 {
+            previous = meyer_cross.attack_sequence.clone();
+            meyer_cross.randomize_with_rules(&mut rng, &rules).unwrap();
+        }
+        let history = meyer_cross.history().iter().collect::<Vec<_>>();
+        assert_eq!(history.len(), history::DrillHistory::CAPACITY);
+        assert_eq!(Some(&history[0].attack_sequence), previous.as_ref());
+    }
+
+    #[test]
+    fn due_transitions_come_up_more_often() {
+        let rules = DrillRules {
+            strikes: HashSet::from([Strike::Long, Strike::Short, Strike::Flat]),
+            ..DrillRules::default()
+        };
+        let weak = Transition {
+            from: Opening::TopRight,
+            to: Opening::BottomLeft,
+            strike: Strike::Flat,
+        };
+        let mut schedule = RepetitionSchedule::default();
+        schedule.review(weak, Recall::Failed);
+
+        let count = |weighted: bool| {
+            let mut rng = StdRng::seed_from_u64(5);
+            let mut meyer_cross = MeyerCross::new();
+            (0..300)
+                .filter(|_| {
+                    if weighted {
+                        meyer_cross.randomize_with_schedule(&mut rng, &rules, &schedule)
+                    } else {
+                        meyer_cross.randomize_with_rules(&mut rng, &rules)
+                    }
+                    .unwrap();
+                    meyer_cross
+                        .attack_sequence
+                        .as_ref()
+                        .unwrap()
+                        .windows(2)
+                        .any(|pair| {
+                            pair[0].opening == weak.from
+                                && pair[1].opening == weak.to
+                                && pair[1].strike == weak.strike
+                        })
+                })
+                .count()
+        };
+        assert!(count(true) > count(false) * 3 / 2);
+    }
+
+    #[test]
+    fn sequences_start_from_the_guard() {
+        let rules = DrillRules {
+            preparations: Guard::ALL.into_iter().map(Preparation::Static).collect(),
+            strikes: HashSet::from([Strike::Long, Strike::Short, Strike::Fehler]),
+            guard_start: true,
+            ..DrillRules::default()
+        };
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut meyer_cross = MeyerCross::new();
+        for _ in 0..100 {
+            meyer_cross.randomize_with_rules(&mut rng, &rules).unwrap();
+            assert!(starts_consistently(
+                meyer_cross.preparation,
+                meyer_cross.attack_sequence.as_ref().unwrap(),
+                &rules.strikes
+            ));
+        }
+    }
 }
@@ -1,6 +1,6 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt};
 
-use rand::seq::SliceRandom;
+use rand::{seq::SliceRandom, Rng};
 use thiserror::Error;
 
 use super::types::Guard;
@@ -9,42 +9,74 @@ use super::types::Guard;
 pub enum PreparationError {
     #[error("No allowed preparation given.")]
     NoPreparation,
+    #[error("Streichen count must be {0} to {1}.")]
+    UnsupportedStreichenCount(usize, usize),
 }
 
-#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub enum Preparation {
     Static(Guard),
     Streichen(StreichenCount),
 }
 
-#[derive(Clone, Copy, Eq, Hash, PartialEq, Debug)]
-pub enum StreichenCount {
-    One,
-    Two,
-    Three,
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Ord, PartialOrd, Debug)]
+pub struct StreichenCount {
+    count: usize,
+}
+
+impl StreichenCount {
+    pub const MIN: usize = 1;
+    pub const MAX: usize = 8;
+
+    pub fn new(count: usize) -> Result<Self, PreparationError> {
+        if (Self::MIN..=Self::MAX).contains(&count) {
+            Ok(Self { count })
+        } else {
+            Err(PreparationError::UnsupportedStreichenCount(
+                Self::MIN,
+                Self::MAX,
+            ))
+        }
+    }
+
+    /// Every supported count, fewest first.
+    pub fn all() -> impl Iterator<Item = StreichenCount> {
+        (Self::MIN..=Self::MAX).map(|count| StreichenCount { count })
+    }
+}
+
+/// Meyer's usual three Streichen.
+impl Default for StreichenCount {
+    fn default() -> Self {
+        StreichenCount { count: 3 }
+    }
 }
 
 pub struct PreparationRandomizer;
 
 pub trait RandomizePreparation {
-    fn gen_random_preparation(
+    fn gen_random_preparation<R: Rng + ?Sized>(
+        rng: &mut R,
         allowed_preparations: &HashSet<Preparation>,
     ) -> Result<Preparation, PreparationError>;
 }
 
 impl RandomizePreparation for PreparationRandomizer {
-    fn gen_random_preparation(
+    fn gen_random_preparation<R: Rng + ?Sized>(
+        rng: &mut R,
         allowed_preparations: &HashSet<Preparation>,
     ) -> Result<Preparation, PreparationError> {
         if allowed_preparations.is_empty() {
             Err(PreparationError::NoPreparation)
         } else {
-            Ok(allowed_preparations
+            let mut allowed_preparations = allowed_preparations
                 .iter()
                 .map(|v| v.to_owned())
-                .collect::<Vec<_>>()
-                .choose(&mut rand::thread_rng())
-                .unwrap_or(&Preparation::Streichen(StreichenCount::Three))
+                .collect::<Vec<_>>();
+            allowed_preparations.sort();
+            Ok(allowed_preparations
+                .choose(rng)
+                .unwrap_or(&Preparation::Streichen(StreichenCount::default()))
                 .to_owned())
         }
     }
@@ -52,10 +84,49 @@ impl RandomizePreparation for PreparationRandomizer {
 
 impl From<StreichenCount> for usize {
     fn from(val: StreichenCount) -> Self {
-        match val {
-            StreichenCount::One => 1,
-            StreichenCount::Two => 2,
-            StreichenCount::Three => 3,
+        val.count
+    }
+}
+
+impl fmt::Display for Preparation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Preparation::Static(guard) => write!(f, "{guard}"),
+            Preparation::Streichen(count) => write!(f, "Streichen {}x", usize::from(*count)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{prelude::*, sample::subsequence};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn all_preparations() -> Vec<Preparation> {
+        StreichenCount::all()
+            .map(Preparation::Streichen)
+            .chain(Guard::ALL.map(Preparation::Static))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn preparations_are_chosen_from_the_allowed(
+            seed in any::<u64>(),
+            allowed in subsequence(all_preparations(), 0..=all_preparations().len()),
+        ) {
+            let allowed = allowed.into_iter().collect::<HashSet<_>>();
+            let result = PreparationRandomizer::gen_random_preparation(
+                &mut StdRng::seed_from_u64(seed),
+                &allowed,
+            );
+            match result {
+                Ok(preparation) => prop_assert!(allowed.contains(&preparation)),
+                Err(PreparationError::NoPreparation) => prop_assert!(allowed.is_empty()),
+                Err(e) => prop_assert!(false, "{e}"),
+            }
         }
     }
 }
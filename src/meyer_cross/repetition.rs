@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use super::types::{parse_name, Opening, Strike, Transition};
+
+#[derive(Error, Debug)]
+pub enum ProfileError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Profile names may only contain letters, digits, '-' and '_'.")]
+    InvalidName,
+    #[error("Not a training profile.")]
+    UnknownFormat,
+    #[error("Invalid profile entry in line {0}.")]
+    InvalidLine(usize),
+}
+
+/// How well the fencer executed a transition.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Recall {
+    Good,
+    Hesitant,
+    Failed,
+}
+
+/// The Leitner box of a transition and the drill it is next due in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Card {
+    pub level: u32,
+    pub due: u64,
+}
+
+/// Schedules transitions the fencer struggled with to come up again soon, and those they know
+/// well ever more rarely. The clock counts drills.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct RepetitionSchedule {
+    clock: u64,
+    cards: HashMap<Transition, Card>,
+}
+
+impl RepetitionSchedule {
+    /// How much more often due transitions come up than others.
+    pub const DUE_WEIGHT: f64 = 5.0;
+    pub const MAX_LEVEL: u32 = 8;
+
+    pub fn clock(&self) -> u64 {
+        self.clock
+    }
+
+    /// Moves on to the next drill.
+    pub fn advance(&mut self) {
+        self.clock = self.clock.saturating_add(1);
+    }
+
+    /// Good recall pushes the transition back twice as far as last time, failing brings it back
+    /// right away.
+    pub fn review(&mut self, transition: Transition, recall: Recall) {
+        let clock = self.clock;
+        let card = self.cards.entry(transition).or_insert(Card {
+            level: 0,
+            due: clock,
+        });
+        match recall {
+            Recall::Good => {
+                card.level = (card.level + 1).min(Self::MAX_LEVEL);
+                card.due = clock.saturating_add(1 << card.level);
+            }
+            Recall::Hesitant => card.due = clock.saturating_add(1),
+            Recall::Failed => {
+                card.level = 0;
+                card.due = clock;
+            }
+        }
+    }
+
+    pub fn is_due(&self, transition: Transition) -> bool {
+        self.cards
+            .get(&transition)
+            .is_some_and(|card| card.due <= self.clock)
+    }
+
+    /// The weight of the transition when generating drills.
+    pub fn weight(&self, transition: Transition) -> f64 {
+        if self.is_due(transition) {
+            Self::DUE_WEIGHT
+        } else {
+            1.0
+        }
+    }
+
+    /// The due transitions, weakest first.
+    pub fn due(&self) -> Vec<(Transition, Card)> {
+        let mut due = self
+            .cards
+            .iter()
+            .filter(|(_, card)| card.due <= self.clock)
+            .map(|(transition, card)| (*transition, *card))
+            .collect::<Vec<_>>();
+        due.sort_by_key(|(transition, card)| {
+            (
+                card.level,
+                card.due,
+                format!("{transition:?}"), // Stable order for equally weak transitions.
+            )
+        });
+        due
+    }
+}
+
+/// The spaced-repetition progress of one fencer.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TrainingProfile {
+    pub name: String,
+    pub schedule: RepetitionSchedule,
+}
+
+const FORMAT: &str = "meyer-cross-profile/1";
+
+impl TrainingProfile {
+    pub const DEFAULT_NAME: &'static str = "default";
+
+    pub fn new(name: &str) -> Result<Self, ProfileError> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(ProfileError::InvalidName);
+        }
+        Ok(TrainingProfile {
+            name: name.to_string(),
+            schedule: RepetitionSchedule::default(),
+        })
+    }
+
+    /// One line per transition, e.g. `TopLeft BottomRight Long 2 14` for level 2, due in drill 14.
+    pub fn encode(&self) -> String {
+        let mut cards = self.schedule.cards.iter().collect::<Vec<_>>();
+        cards.sort_by_key(|(transition, _)| format!("{transition:?}"));
+
+        let mut text = format!("{FORMAT}\nclock {}\n", self.schedule.clock);
+        for (transition, card) in cards {
+            text += &format!(
+                "{:?} {:?} {:?} {} {}\n",
+                transition.from, transition.to, transition.strike, card.level, card.due
+            );
+        }
+        text
+    }
+
+    pub fn decode(name: &str, text: &str) -> Result<Self, ProfileError> {
+        let mut profile = Self::new(name)?;
+        let mut lines = text.lines();
+        if lines.next() != Some(FORMAT) {
+            return Err(ProfileError::UnknownFormat);
+        }
+        for (index, line) in lines.enumerate() {
+            let invalid = || ProfileError::InvalidLine(index + 2);
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            match words[..] {
+                [] => {}
+                ["clock", clock] => {
+                    profile.schedule.clock = clock.parse().map_err(|_| invalid())?
+                }
+                [from, to, strike, level, due] => {
+                    let transition = Transition {
+                        from: parse_name(&Opening::ALL, from).ok_or_else(invalid)?,
+                        to: parse_name(&Opening::ALL, to).ok_or_else(invalid)?,
+                        strike: parse_name(&Strike::ALL, strike).ok_or_else(invalid)?,
+                    };
+                    let card = Card {
+                        level: level
+                            .parse()
+                            .ok()
+                            .filter(|&level| level <= RepetitionSchedule::MAX_LEVEL)
+                            .ok_or_else(invalid)?,
+                        due: due.parse().map_err(|_| invalid())?,
+                    };
+                    profile.schedule.cards.insert(transition, card);
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(profile)
+    }
+}
+
+/// Profiles are kept as text files next to the app, browsers have no file system.
+#[cfg(not(target_arch = "wasm32"))]
+impl TrainingProfile {
+    const DIRECTORY: &'static str = "profiles";
+
+    fn path(name: &str) -> std::path::PathBuf {
+        std::path::Path::new(Self::DIRECTORY).join(format!("{name}.txt"))
+    }
+
+    /// Loads the profile, or starts a new one if there is none by that name yet.
+    pub fn load(name: &str) -> Result<Self, ProfileError> {
+        let profile = Self::new(name)?;
+        match std::fs::read_to_string(Self::path(name)) {
+            Ok(text) => Self::decode(name, &text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(profile),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), ProfileError> {
+        std::fs::create_dir_all(Self::DIRECTORY)?;
+        std::fs::write(Self::path(&self.name), self.encode())?;
+        Ok(())
+    }
+}
+
+impl Default for TrainingProfile {
+    fn default() -> Self {
+        TrainingProfile {
+            name: Self::DEFAULT_NAME.to_string(),
+            schedule: RepetitionSchedule::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSITION: Transition = Transition {
+        from: Opening::TopLeft,
+        to: Opening::BottomRight,
+        strike: Strike::Long,
+    };
+
+    #[test]
+    fn failed_transitions_are_due_until_recalled() {
+        let mut schedule = RepetitionSchedule::default();
+        assert_eq!(schedule.weight(TRANSITION), 1.0);
+
+        schedule.review(TRANSITION, Recall::Failed);
+        schedule.advance();
+        assert_eq!(schedule.weight(TRANSITION), RepetitionSchedule::DUE_WEIGHT);
+
+        schedule.review(TRANSITION, Recall::Good);
+        assert!(!schedule.is_due(TRANSITION));
+        schedule.advance();
+        assert!(!schedule.is_due(TRANSITION));
+        schedule.advance();
+        assert_eq!(
+            schedule.due(),
+            vec![(TRANSITION, Card { level: 1, due: 3 })]
+        );
+    }
+
+    #[test]
+    fn profiles_survive_encoding() {
+        let mut profile = TrainingProfile::new("anna_1").unwrap();
+        profile.schedule.review(TRANSITION, Recall::Hesitant);
+        profile.schedule.advance();
+
+        let decoded = TrainingProfile::decode("anna_1", &profile.encode()).unwrap();
+        assert_eq!(decoded, profile);
+        assert!(matches!(
+            TrainingProfile::new("../anna"),
+            Err(ProfileError::InvalidName)
+        ));
+        let corrupt = format!("{FORMAT}\nTopLeft BottomRight Long 4294967295 0");
+        assert!(matches!(
+            TrainingProfile::decode("anna_1", &corrupt),
+            Err(ProfileError::InvalidLine(2))
+        ));
+
+        let mut late = TrainingProfile::decode("anna_1", &format!("{FORMAT}\nclock {}", u64::MAX))
+            .unwrap()
+            .schedule;
+        late.review(TRANSITION, Recall::Good);
+        late.advance();
+        assert_eq!(late.clock(), u64::MAX);
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashSet;
+
+use super::{
+    attack_sequence::{AttackSequenceError, AttackSequences},
+    defense::Defense,
+    fehler::FehlerTarget,
+    history::RepeatAvoidance,
+    markov::MarkovGenerator,
+    preparation::{Preparation, StreichenCount},
+    types::Strike,
+};
+
+/// Everything the random generation of a drill can be configured with.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DrillRules {
+    pub preparations: HashSet<Preparation>,
+    pub strikes: HashSet<Strike>,
+    pub min_length: usize,
+    pub max_length: usize,
+    pub doppelfehler_enabled: bool,
+    pub left_handed: bool,
+    pub repeat_avoidance: RepeatAvoidance,
+    /// Number of recent drills, including the current one, a new drill must not repeat.
+    pub repeat_window: usize,
+    /// Draws sequences from a Markov chain instead of following the base sequences.
+    pub markov: Option<MarkovGenerator>,
+    /// Starts the sequence with an attack that fits the guard of the preparation.
+    pub guard_start: bool,
+    pub fehler_target: FehlerTarget,
+    /// The responses the partner is drilled with, none for fencing alone.
+    pub defenses: HashSet<Defense>,
+}
+
+impl Default for DrillRules {
+    fn default() -> Self {
+        DrillRules {
+            preparations: HashSet::from([Preparation::Streichen(StreichenCount::default())]),
+            strikes: HashSet::from([Strike::Long]),
+            min_length: 4,
+            max_length: 4,
+            doppelfehler_enabled: false,
+            left_handed: false,
+            repeat_avoidance: RepeatAvoidance::Off,
+            repeat_window: 3,
+            markov: None,
+            guard_start: false,
+            fehler_target: FehlerTarget::default(),
+            defenses: HashSet::new(),
+        }
+    }
+}
+
+impl DrillRules {
+    /// Every attack sequence the rules allow, before mirroring for left-handed fencers.
+    pub fn attack_sequences(&self) -> Result<AttackSequences, AttackSequenceError> {
+        AttackSequences::new(
+            self.min_length,
+            self.max_length,
+            &self.strikes,
+            self.doppelfehler_enabled,
+        )
+    }
+}
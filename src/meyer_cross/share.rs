@@ -0,0 +1,337 @@
+use std::collections::HashSet;
+
+use num_traits::FromPrimitive;
+use thiserror::Error;
+
+use super::{
+    attack_sequence::AttackSequenceLength,
+    defense::Defense,
+    fehler::FehlerTarget,
+    preparation::{Preparation, StreichenCount},
+    rules::DrillRules,
+    types::{Attack, Guard, Opening, Strike},
+    MeyerCross,
+};
+
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum ShareLinkError {
+    #[error("The link contains no drill.")]
+    NoDrill,
+    #[error("Invalid {0} in link.")]
+    Invalid(&'static str),
+}
+
+/// A drill, optionally with its rules and seed, encoded as URL query or fragment like
+/// `drill=s3_blcsdl&rules=s3g0_lf_4-6_dh&seed=42`.
+#[derive(Clone, Debug)]
+pub struct ShareLink {
+    pub drill: MeyerCross,
+    pub rules: Option<DrillRules>,
+    pub seed: Option<u64>,
+}
+
+const OPENING_CODES: [(Opening, char); 4] = [
+    (Opening::TopLeft, 'a'),
+    (Opening::TopRight, 'b'),
+    (Opening::BottomLeft, 'c'),
+    (Opening::BottomRight, 'd'),
+];
+
+const STRIKE_CODES: [(Strike, char); 4] = [
+    (Strike::Long, 'l'),
+    (Strike::Short, 's'),
+    (Strike::Flat, 'f'),
+    (Strike::Fehler, 'x'),
+];
+
+const DEFENSE_CODES: [(Defense, char); 3] = [
+    (Defense::Parry, 'p'),
+    (Defense::Versetzen, 'v'),
+    (Defense::CounterCut, 'k'),
+];
+
+fn code<T: PartialEq>(codes: &[(T, char)], value: T) -> char {
+    codes
+        .iter()
+        .find(|(v, _)| *v == value)
+        .map_or('?', |(_, c)| *c)
+}
+
+fn decode<T: Copy>(codes: &[(T, char)], c: char, what: &'static str) -> Result<T, ShareLinkError> {
+    codes
+        .iter()
+        .find(|(_, code)| *code == c)
+        .map(|(v, _)| *v)
+        .ok_or(ShareLinkError::Invalid(what))
+}
+
+fn encode_preparation(preparation: Preparation) -> String {
+    match preparation {
+        Preparation::Static(guard) => format!("g{}", guard as usize),
+        Preparation::Streichen(count) => format!("s{}", usize::from(count)),
+    }
+}
+
+/// Decodes a run of preparations like `s3g0g7`.
+fn decode_preparations(text: &str) -> Result<Vec<Preparation>, ShareLinkError> {
+    let invalid = ShareLinkError::Invalid("preparation");
+    let mut preparations = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(kind) = chars.next() {
+        let mut number = String::new();
+        while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+            number.push(digit);
+        }
+        let number = number.parse::<usize>().map_err(|_| invalid.clone())?;
+        preparations.push(match (kind, number) {
+            ('s', n) => {
+                Preparation::Streichen(StreichenCount::new(n).map_err(|_| invalid.clone())?)
+            }
+            ('g', n) => Preparation::Static(Guard::from_usize(n).ok_or(invalid.clone())?),
+            _ => return Err(invalid),
+        });
+    }
+    Ok(preparations)
+}
+
+impl ShareLink {
+    pub fn encode(&self) -> String {
+        let attacks = self
+            .drill
+            .attack_sequence
+            .iter()
+            .flatten()
+            .flat_map(|a| {
+                [
+                    Some(code(&OPENING_CODES, a.opening)),
+                    Some(code(&STRIKE_CODES, a.strike)),
+                    a.target
+                        .map(|target| code(&OPENING_CODES, target).to_ascii_uppercase()),
+                    a.response.map(|response| code(&DEFENSE_CODES, response)),
+                ]
+            })
+            .flatten()
+            .collect::<String>();
+        let mut link = format!(
+            "drill={}_{attacks}",
+            self.drill
+                .preparation
+                .map_or(String::new(), encode_preparation)
+        );
+
+        if let Some(rules) = &self.rules {
+            link += &format!("&rules={}", encode_rules(rules));
+        }
+
+        if let Some(seed) = self.seed {
+            link += &format!("&seed={seed}");
+        }
+        link
+    }
+
+    /// Parses the query or fragment of a link, with or without the leading `?` or `#`.
+    pub fn decode(text: &str) -> Result<Self, ShareLinkError> {
+        let mut drill = None;
+        let mut rules = None;
+        let mut seed = None;
+        let text = text.rsplit(['?', '#']).next().unwrap_or_default();
+        for (key, value) in text.split('&').filter_map(|pair| pair.split_once('=')) {
+            match key {
+                "drill" => drill = Some(Self::decode_drill(value)?),
+                "rules" => rules = Some(decode_rules(value)?),
+                "seed" => seed = Some(value.parse().map_err(|_| ShareLinkError::Invalid("seed"))?),
+                _ => (),
+            }
+        }
+        let drill = drill.ok_or(ShareLinkError::NoDrill)?;
+        if let Some(rules) = &rules {
+            check_rules(drill.attack_sequence.as_deref().unwrap_or_default(), rules)?;
+        }
+        Ok(ShareLink { drill, rules, seed })
+    }
+
+    fn decode_drill(value: &str) -> Result<MeyerCross, ShareLinkError> {
+        let (preparation, attacks) = value
+            .split_once('_')
+            .ok_or(ShareLinkError::Invalid("drill"))?;
+        // Each attack is an opening and a strike, a Fehler target follows in upper case and the
+        // partner's response last.
+        let mut chars = attacks.chars().peekable();
+        let mut attack_sequence = vec![];
+        while let Some(opening) = chars.next() {
+            let strike = chars
+                .next()
+                .ok_or(ShareLinkError::Invalid("attack sequence"))?;
+            let target = chars
+                .next_if(char::is_ascii_uppercase)
+                .map(|c| decode(&OPENING_CODES, c.to_ascii_lowercase(), "Fehler target"))
+                .transpose()?;
+            let response = chars
+                .next_if(|c| DEFENSE_CODES.iter().any(|(_, code)| code == c))
+                .map(|c| decode(&DEFENSE_CODES, c, "response"))
+                .transpose()?;
+            let attack = Attack {
+                opening: decode(&OPENING_CODES, opening, "opening")?,
+                strike: decode(&STRIKE_CODES, strike, "strike")?,
+                target,
+                response,
+            };
+            // Only a Fehler has a target, and it strikes another opening than the feint.
+            if target.is_some_and(|t| attack.strike != Strike::Fehler || t == attack.opening) {
+                return Err(ShareLinkError::Invalid("Fehler target"));
+            }
+            attack_sequence.push(attack);
+        }
+        if !(AttackSequenceLength::MIN_LENGTH..=AttackSequenceLength::MAX_LENGTH)
+            .contains(&attack_sequence.len())
+            || attack_sequence
+                .last()
+                .is_some_and(|attack| attack.strike == Strike::Fehler)
+        {
+            return Err(ShareLinkError::Invalid("attack sequence"));
+        }
+
+        Ok(MeyerCross {
+            preparation: decode_preparations(preparation)?.first().copied(),
+            attack_sequence: Some(attack_sequence),
+            history: Default::default(),
+        })
+    }
+}
+
+/// Rejects drills the rules could not have made: Doppelfehler while they are off, or responses
+/// the rules do not give.
+fn check_rules(attacks: &[Attack], rules: &DrillRules) -> Result<(), ShareLinkError> {
+    let doppelfehler = attacks
+        .windows(2)
+        .any(|pair| pair.iter().all(|attack| attack.strike == Strike::Fehler));
+    if doppelfehler && !rules.doppelfehler_enabled {
+        return Err(ShareLinkError::Invalid("attack sequence"));
+    }
+    if attacks
+        .iter()
+        .filter_map(|attack| attack.response)
+        .any(|response| !rules.defenses.contains(&response))
+    {
+        return Err(ShareLinkError::Invalid("response"));
+    }
+    Ok(())
+}
+
+/// Encodes the rules like `s3g0_lf_4-6_dhgpv`: preparations, strikes, length range and flags,
+/// the partner's responses among them.
+pub fn encode_rules(rules: &DrillRules) -> String {
+    let mut preparations = rules.preparations.iter().copied().collect::<Vec<_>>();
+    preparations.sort();
+    let mut strikes = rules.strikes.iter().copied().collect::<Vec<_>>();
+    strikes.sort();
+    let mut defenses = rules.defenses.iter().copied().collect::<Vec<_>>();
+    defenses.sort();
+    format!(
+        "{}_{}_{}-{}_{}{}{}{}{}",
+        preparations
+            .into_iter()
+            .map(encode_preparation)
+            .collect::<String>(),
+        strikes
+            .into_iter()
+            .map(|s| code(&STRIKE_CODES, s))
+            .collect::<String>(),
+        rules.min_length,
+        rules.max_length,
+        if rules.doppelfehler_enabled { "d" } else { "" },
+        if rules.left_handed { "h" } else { "" },
+        if rules.guard_start { "g" } else { "" },
+        match rules.fehler_target {
+            FehlerTarget::HighLow => "",
+            FehlerTarget::OtherSide => "o",
+            FehlerTarget::Diagonal => "x",
+        },
+        defenses
+            .into_iter()
+            .map(|d| code(&DEFENSE_CODES, d))
+            .collect::<String>(),
+    )
+}
+
+pub fn decode_rules(value: &str) -> Result<DrillRules, ShareLinkError> {
+    let invalid = || ShareLinkError::Invalid("rules");
+    let parts = value.split('_').collect::<Vec<_>>();
+    let [preparations, strikes, lengths, flags] = parts[..] else {
+        return Err(invalid());
+    };
+    let (min, max) = lengths.split_once('-').ok_or_else(invalid)?;
+    let lengths = AttackSequenceLength::MIN_LENGTH..=AttackSequenceLength::MAX_LENGTH;
+    let min_length = min.parse().ok().filter(|min| lengths.contains(min));
+    let max_length = max.parse().ok().filter(|max| lengths.contains(max));
+    let (Some(min_length), Some(max_length)) = (min_length, max_length) else {
+        return Err(invalid());
+    };
+    if min_length > max_length {
+        return Err(invalid());
+    }
+    Ok(DrillRules {
+        preparations: decode_preparations(preparations)?.into_iter().collect(),
+        strikes: strikes
+            .chars()
+            .map(|c| decode(&STRIKE_CODES, c, "strike"))
+            .collect::<Result<HashSet<_>, _>>()?,
+        min_length,
+        max_length,
+        doppelfehler_enabled: flags.contains('d'),
+        left_handed: flags.contains('h'),
+        guard_start: flags.contains('g'),
+        fehler_target: if flags.contains('o') {
+            FehlerTarget::OtherSide
+        } else if flags.contains('x') {
+            FehlerTarget::Diagonal
+        } else {
+            FehlerTarget::HighLow
+        },
+        defenses: DEFENSE_CODES
+            .iter()
+            .filter(|(_, code)| flags.contains(*code))
+            .map(|(defense, _)| *defense)
+            .collect(),
+        ..DrillRules::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_with_impossible_lengths_are_rejected() {
+        let rules = decode_rules("s3_ls_4-6_d").unwrap();
+        assert_eq!((rules.min_length, rules.max_length), (4, 6));
+        for lengths in ["1-99", "6-4", "1-4", "x-4"] {
+            assert_eq!(
+                decode_rules(&format!("s3_ls_{lengths}_")),
+                Err(ShareLinkError::Invalid("rules")),
+                "{lengths}"
+            );
+        }
+    }
+    #[test]
+    fn drills_the_rules_cannot_make_are_rejected() {
+        let drill = ShareLink::decode("drill=s3_axBcs").unwrap().drill;
+        let attacks = drill.attack_sequence.unwrap();
+        assert_eq!(attacks[0].target, Some(Opening::TopRight));
+        for (link, error) in [
+            ("drill=_alBcs", "Fehler target"),
+            ("drill=_axAcs", "Fehler target"),
+            ("drill=_alcxB", "attack sequence"),
+            ("drill=_axBbxCal&rules=_lx_3-3_", "attack sequence"),
+            ("drill=_alpcs&rules=_ls_2-2_", "response"),
+        ] {
+            assert_eq!(
+                ShareLink::decode(link).err(),
+                Some(ShareLinkError::Invalid(error)),
+                "{link}"
+            );
+        }
+        assert!(ShareLink::decode("drill=_axBbxCal&rules=_lx_3-3_d").is_ok());
+        assert!(ShareLink::decode("drill=_alpcs&rules=_ls_2-2_p").is_ok());
+    }
+}
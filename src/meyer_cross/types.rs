@@ -1,3 +1,5 @@
+use std::fmt;
+
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use rand::{
@@ -5,7 +7,9 @@ use rand::{
     Rng,
 };
 
-#[derive(FromPrimitive, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+use super::defense::Defense;
+
+#[derive(FromPrimitive, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Hash, Debug)]
 pub enum Strike {
     Flat,
     Long,
@@ -13,7 +17,7 @@ pub enum Strike {
     Fehler,
 }
 
-#[derive(FromPrimitive, Clone, Copy, Hash, Eq, PartialEq, Debug)]
+#[derive(FromPrimitive, Clone, Copy, Hash, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub enum Guard {
     TagLeft,
     TagRight,
@@ -27,7 +31,7 @@ pub enum Guard {
     Alber,
 }
 
-#[derive(FromPrimitive, Clone, Copy, Hash, PartialEq, Debug)]
+#[derive(FromPrimitive, Clone, Copy, Hash, Eq, PartialEq, Debug)]
 pub enum Opening {
     TopLeft,
     TopRight,
@@ -39,6 +43,63 @@ pub enum Opening {
 pub struct Attack {
     pub opening: Opening,
     pub strike: Strike,
+    /// Where a Fehler really strikes after feinting to `opening`.
+    pub target: Option<Opening>,
+    /// What the partner answers the attack with in two-role drills.
+    pub response: Option<Defense>,
+}
+
+impl Attack {
+    /// A plain attack, without Fehler target or partner response.
+    pub fn new(opening: Opening, strike: Strike) -> Self {
+        Attack {
+            opening,
+            strike,
+            target: None,
+            response: None,
+        }
+    }
+}
+
+/// Moving from one opening to the next with the given strike.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Transition {
+    pub from: Opening,
+    pub to: Opening,
+    pub strike: Strike,
+}
+
+impl Strike {
+    pub const ALL: [Strike; 4] = [Strike::Long, Strike::Short, Strike::Flat, Strike::Fehler];
+}
+
+impl Guard {
+    pub const ALL: [Guard; 10] = [
+        Guard::TagLeft,
+        Guard::TagRight,
+        Guard::TagAbove,
+        Guard::PflugLeft,
+        Guard::PflugRight,
+        Guard::OchsLeft,
+        Guard::OchsRight,
+        Guard::Eisenport,
+        Guard::Langort,
+        Guard::Alber,
+    ];
+}
+
+impl Opening {
+    pub const ALL: [Opening; 4] = [
+        Opening::TopLeft,
+        Opening::TopRight,
+        Opening::BottomLeft,
+        Opening::BottomRight,
+    ];
+}
+
+/// Looks up the variant by its `Debug` name, which the profile and matrix files use.
+pub fn parse_name<T: Copy + fmt::Debug>(all: &[T], name: &str) -> Option<T> {
+    all.iter().find(|v| format!("{v:?}") == name).copied()
 }
 
 macro_rules! impl_standard_distribution {
@@ -54,3 +115,43 @@ macro_rules! impl_standard_distribution {
 
 impl_standard_distribution!(Opening, Opening::BottomRight as usize);
 impl_standard_distribution!(Guard, Guard::Alber as usize);
+impl_standard_distribution!(Strike, Strike::Fehler as usize);
+
+impl fmt::Display for Strike {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Strike::Flat => "Flat",
+            Strike::Long => "Long Edge",
+            Strike::Short => "Short Edge",
+            Strike::Fehler => "Fehler",
+        })
+    }
+}
+
+impl fmt::Display for Guard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Guard::TagLeft => "Tag Left",
+            Guard::TagRight => "Tag Right",
+            Guard::TagAbove => "Tag Above",
+            Guard::PflugLeft => "Pflug Left",
+            Guard::PflugRight => "Pflug Right",
+            Guard::OchsLeft => "Ochs Left",
+            Guard::OchsRight => "Ochs Right",
+            Guard::Eisenport => "Eisenport",
+            Guard::Langort => "Langort",
+            Guard::Alber => "Alber",
+        })
+    }
+}
+
+impl fmt::Display for Opening {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Opening::TopLeft => "Top Left",
+            Opening::TopRight => "Top Right",
+            Opening::BottomLeft => "Bottom Left",
+            Opening::BottomRight => "Bottom Right",
+        })
+    }
+}
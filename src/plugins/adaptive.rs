@@ -0,0 +1,103 @@
+use bevy::{app::Plugin, prelude::*};
+
+use crate::{
+    gui::setup_egui::UiState,
+    resources::{
+        meyer_cross::MeyerCross,
+        progression::Progression,
+        reaction_log::{ReactionCursor, ReactionLog},
+        session_log::{SelfRating, SessionLog},
+    },
+};
+
+use super::reaction::start_session;
+
+/// Adjusts the rules to the fencer, judged by self-ratings in flash mode or by reaction times.
+pub struct AdaptivePlugin;
+
+impl Plugin for AdaptivePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Progression::default()).add_systems(
+            Update,
+            (
+                record_ratings,
+                record_reactions.after(start_session),
+                apply_level,
+            )
+                .chain(),
+        );
+    }
+}
+
+fn rating_score(rating: SelfRating) -> f32 {
+    match rating {
+        SelfRating::Good => 1.0,
+        SelfRating::Unsure => 0.5,
+        SelfRating::Wrong => 0.0,
+    }
+}
+
+fn record_ratings(
+    ui: Res<UiState>,
+    log: Res<SessionLog>,
+    mut progression: ResMut<Progression>,
+    mut cursor: Local<usize>,
+) {
+    let new_entries = log.read_new(&mut cursor);
+    if !ui.adaptive_difficulty {
+        return;
+    }
+
+    for entry in new_entries {
+        let scores = entry
+            .attacks
+            .iter()
+            .filter_map(|attack| attack.rating.map(rating_score))
+            .collect::<Vec<_>>();
+        if !scores.is_empty() {
+            progression.record(scores.iter().sum::<f32>() / scores.len() as f32);
+        }
+    }
+}
+
+/// Judges each finished drill by the share of cues answered within the target time.
+fn record_reactions(
+    ui: Res<UiState>,
+    meyer_cross: Res<MeyerCross>,
+    log: Res<ReactionLog>,
+    mut progression: ResMut<Progression>,
+    mut cursor: Local<ReactionCursor>,
+) {
+    if !meyer_cross.is_changed() {
+        return;
+    }
+    let (samples, missed) = log.read_new(&mut cursor);
+    if !ui.adaptive_difficulty || !ui.reaction_measurement || ui.flash_mode {
+        return;
+    }
+
+    let cues = samples.len() + missed;
+    if cues > 0 {
+        let fast = samples
+            .iter()
+            .filter(|sample| sample.latency_s <= ui.reaction_target_s)
+            .count();
+        progression.record(fast as f32 / cues as f32);
+    }
+}
+
+fn apply_level(
+    progression: Res<Progression>,
+    mut ui: ResMut<UiState>,
+    mut was_adaptive: Local<bool>,
+) {
+    let adaptive = ui.adaptive_difficulty;
+    if adaptive && (progression.is_changed() || !*was_adaptive) {
+        let mut rules = ui.rules();
+        progression.apply_to(&mut rules);
+        if rules != ui.rules() {
+            ui.apply_rules(&rules);
+        }
+    }
+    *was_adaptive = adaptive;
+}
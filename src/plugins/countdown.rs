@@ -0,0 +1,51 @@
+use crate::{
+    gui::setup_egui::UiState,
+    resources::{
+        countdown::{AttackCued, Countdown},
+        meyer_cross::MeyerCross,
+    },
+};
+use bevy::{app::Plugin, prelude::*};
+
+pub struct CountdownPlugin;
+
+impl Plugin for CountdownPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Countdown::default())
+            .add_event::<AttackCued>()
+            .add_systems(Update, (restart_countdown, tick_countdown).chain());
+    }
+}
+
+fn restart_countdown(
+    meyer_cross: Res<MeyerCross>,
+    ui: Res<UiState>,
+    mut countdown: ResMut<Countdown>,
+) {
+    if countdown.is_following() {
+        return;
+    }
+
+    if !ui.timer_active {
+        if countdown.is_active() {
+            countdown.stop();
+        }
+    } else if meyer_cross.is_changed() || !countdown.is_active() {
+        countdown.start(ui.time_for_preparation_s as f32);
+    }
+}
+
+fn tick_countdown(
+    time: Res<Time>,
+    meyer_cross: Res<MeyerCross>,
+    mut ui: ResMut<UiState>,
+    mut countdown: ResMut<Countdown>,
+    mut cues: EventWriter<AttackCued>,
+) {
+    let attack_count = meyer_cross.attack_sequence.as_ref().map_or(0, Vec::len);
+    let revealed = countdown.revealed();
+    if countdown.tick(time.delta(), attack_count, ui.cue_timing()) {
+        ui.apply_pending = true;
+    }
+    cues.send_batch((revealed..countdown.revealed()).map(|index| AttackCued { index }));
+}
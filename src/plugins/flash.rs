@@ -0,0 +1,30 @@
+use crate::{
+    gui::setup_egui::UiState,
+    resources::{flash::Flash, meyer_cross::MeyerCross, session_log::SessionLog},
+};
+use bevy::{app::Plugin, prelude::*};
+
+pub struct FlashPlugin;
+
+impl Plugin for FlashPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Flash::default())
+            .insert_resource(SessionLog::default())
+            .add_systems(Update, (restart_flash, tick_flash).chain());
+    }
+}
+
+fn restart_flash(meyer_cross: Res<MeyerCross>, ui: Res<UiState>, mut flash: ResMut<Flash>) {
+    if !ui.flash_mode {
+        if flash.is_active() {
+            flash.stop();
+        }
+    } else if meyer_cross.is_changed() || !flash.is_active() {
+        let attack_count = meyer_cross.attack_sequence.as_ref().map_or(0, Vec::len);
+        flash.start(ui.flash_show_s as f32, attack_count);
+    }
+}
+
+fn tick_flash(time: Res<Time>, mut flash: ResMut<Flash>) {
+    flash.tick(time.delta());
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use bevy::{app::Plugin, prelude::*};
+
+use crate::{
+    lan::{LanClient, SyncState},
+    resources::{
+        countdown::{AttackCued, Countdown},
+        lan_sync::LanSync,
+        meyer_cross::MeyerCross,
+    },
+};
+
+/// Resent even without changes, so late clients catch up and lost packets do not matter.
+const HEARTBEAT: Duration = Duration::from_millis(500);
+
+pub struct LanSyncPlugin;
+
+impl Plugin for LanSyncPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LanSync::default())
+            .add_systems(Update, (broadcast_state, follow_host));
+    }
+}
+
+fn broadcast_state(
+    time: Res<Time>,
+    lan: Res<LanSync>,
+    meyer_cross: Res<MeyerCross>,
+    countdown: Res<Countdown>,
+    mut drill_version: Local<u64>,
+    mut sent: Local<Option<SyncState>>,
+    mut since_sent: Local<Duration>,
+) {
+    if meyer_cross.is_changed() {
+        *drill_version += 1;
+    }
+    let Some(host) = lan.host() else {
+        return;
+    };
+    if meyer_cross.attack_sequence.is_none() {
+        return;
+    }
+
+    let state = SyncState {
+        drill_version: *drill_version,
+        drill: meyer_cross.clone(),
+        phase: countdown.phase(),
+        revealed: countdown.revealed(),
+    };
+    *since_sent += time.delta();
+    if sent.as_ref() != Some(&state) || *since_sent >= HEARTBEAT {
+        if let Err(e) = host.send(&state) {
+            warn!("Group drill not sent: {e}");
+        }
+        *sent = Some(state);
+        *since_sent = Duration::ZERO;
+    }
+}
+
+fn follow_host(
+    lan: Res<LanSync>,
+    mut meyer_cross: ResMut<MeyerCross>,
+    mut countdown: ResMut<Countdown>,
+    mut cues: EventWriter<AttackCued>,
+    mut drill_version: Local<Option<u64>>,
+) {
+    let Some(state) = lan.client().and_then(LanClient::receive) else {
+        return;
+    };
+
+    let mut revealed = countdown.revealed();
+    if *drill_version != Some(state.drill_version) || *meyer_cross != state.drill {
+        *drill_version = Some(state.drill_version);
+        meyer_cross.replace(state.drill);
+        revealed = 0;
+    }
+    if !countdown.is_following()
+        || countdown.phase() != state.phase
+        || countdown.revealed() != state.revealed
+    {
+        countdown.follow(state.phase, state.revealed);
+        cues.send_batch((revealed..state.revealed).map(|index| AttackCued { index }));
+    }
+}
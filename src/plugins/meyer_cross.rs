@@ -1,10 +1,16 @@
-use crate::resources::meyer_cross::MeyerCross;
+use crate::resources::{
+    drill_rng::DrillRng,
+    meyer_cross::{MeyerCross, QueuedDrill, SequenceWalk},
+};
 use bevy::{app::Plugin, prelude::*};
 
 pub struct MeyerCrossPlugin;
 
 impl Plugin for MeyerCrossPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(MeyerCross::new());
+        app.insert_resource(MeyerCross::new())
+            .insert_resource(QueuedDrill::default())
+            .insert_resource(SequenceWalk::default())
+            .insert_resource(DrillRng::default());
     }
 }
@@ -1 +1,10 @@
+pub mod adaptive;
+pub mod countdown;
+pub mod flash;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod lan_sync;
 pub mod meyer_cross;
+pub mod reaction;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod repetition;
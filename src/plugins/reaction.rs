@@ -0,0 +1,73 @@
+use crate::{
+    gui::setup_egui::UiState,
+    resources::{countdown::AttackCued, meyer_cross::MeyerCross, reaction_log::ReactionLog},
+};
+use bevy::{app::Plugin, prelude::*};
+
+pub struct ReactionPlugin;
+
+impl Plugin for ReactionPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ReactionLog::default()).add_systems(
+            Update,
+            (start_session, record_cues, confirm_reactions).chain(),
+        );
+    }
+}
+
+fn is_measuring(ui: &UiState) -> bool {
+    ui.reaction_measurement && ui.timer_active
+}
+
+pub fn start_session(
+    ui: Res<UiState>,
+    meyer_cross: Res<MeyerCross>,
+    mut log: ResMut<ReactionLog>,
+    mut was_measuring: Local<bool>,
+) {
+    let measuring = is_measuring(&ui);
+    if measuring && !*was_measuring {
+        log.clear();
+    } else if measuring && meyer_cross.is_changed() {
+        log.drop_pending();
+    }
+    *was_measuring = measuring;
+}
+
+fn record_cues(
+    time: Res<Time>,
+    ui: Res<UiState>,
+    meyer_cross: Res<MeyerCross>,
+    mut log: ResMut<ReactionLog>,
+    mut cues: EventReader<AttackCued>,
+) {
+    for cue in cues.iter() {
+        let attacks = meyer_cross.attack_sequence.as_deref().unwrap_or_default();
+        let previous = cue
+            .index
+            .checked_sub(1)
+            .and_then(|index| attacks.get(index));
+        if let Some(&attack) = attacks.get(cue.index).filter(|_| is_measuring(&ui)) {
+            log.cue(
+                cue.index,
+                attack,
+                previous.copied(),
+                time.elapsed_seconds_f64(),
+            );
+        }
+    }
+}
+
+fn confirm_reactions(
+    time: Res<Time>,
+    ui: Res<UiState>,
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<GamepadButton>>,
+    mut log: ResMut<ReactionLog>,
+) {
+    let pressed = keys.any_just_pressed([KeyCode::Space, KeyCode::Return])
+        || buttons.get_just_pressed().next().is_some();
+    if pressed && is_measuring(&ui) {
+        log.confirm(time.elapsed_seconds_f64());
+    }
+}
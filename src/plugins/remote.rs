@@ -0,0 +1,92 @@
+use bevy::{app::Plugin, prelude::*};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    gui::setup_egui::UiState,
+    remote::{drill_json, RemoteCommand, RemoteServer, DEFAULT_ADDRESS, LAN_ADDRESS},
+    resources::{countdown::Countdown, meyer_cross::MeyerCross},
+};
+
+impl Resource for RemoteServer {}
+
+/// Lets a phone in the same network control the drills through the embedded web page.
+pub struct RemotePlugin {
+    pub address: String,
+}
+
+impl RemotePlugin {
+    /// Only listens on this computer unless `lan` opts into the network.
+    pub fn new(lan: bool) -> Self {
+        RemotePlugin {
+            address: if lan { LAN_ADDRESS } else { DEFAULT_ADDRESS }.to_string(),
+        }
+    }
+}
+
+impl Default for RemotePlugin {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl Plugin for RemotePlugin {
+    fn build(&self, app: &mut App) {
+        match RemoteServer::start(self.address.as_str()) {
+            Ok(server) => {
+                info!("Remote control on {}", server.url());
+                app.insert_resource(server).add_systems(
+                    Update,
+                    (
+                        (apply_remote_commands, publish_drill).chain(),
+                        show_remote_link,
+                    ),
+                );
+            }
+            Err(e) => error!("Remote control not available: {e}"),
+        }
+    }
+}
+
+fn apply_remote_commands(
+    server: Res<RemoteServer>,
+    mut ui: ResMut<UiState>,
+    mut countdown: ResMut<Countdown>,
+) {
+    for command in server.commands() {
+        match command {
+            RemoteCommand::Regenerate => ui.apply_pending = true,
+            RemoteCommand::Pause => countdown.set_paused(true),
+            RemoteCommand::Resume => countdown.set_paused(false),
+            RemoteCommand::Rules(rules) => {
+                ui.apply_rules(&rules);
+                ui.apply_pending = true;
+            }
+        }
+    }
+}
+
+fn publish_drill(
+    server: Res<RemoteServer>,
+    meyer_cross: Res<MeyerCross>,
+    ui: Res<UiState>,
+    countdown: Res<Countdown>,
+    mut published: Local<String>,
+) {
+    let json = drill_json(&meyer_cross, &ui.rules(), countdown.is_paused());
+    if *published != json {
+        server.publish(json.clone());
+        *published = json;
+    }
+}
+
+/// Shows the link with the token, only pages opened through it can control the drills.
+fn show_remote_link(server: Res<RemoteServer>, mut contexts: EguiContexts) {
+    egui::Window::new("Remote Control")
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.add(egui::TextEdit::singleline(&mut server.url().as_str()));
+            if server.address().ip().is_unspecified() {
+                ui.label("Replace 0.0.0.0 with this computer's address in the network.");
+            }
+        });
+}
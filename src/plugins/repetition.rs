@@ -0,0 +1,123 @@
+use bevy::{app::Plugin, prelude::*};
+
+use crate::{
+    gui::setup_egui::UiState,
+    meyer_cross::{
+        mirror::Mirror,
+        repetition::Recall,
+        types::{Attack, Transition},
+    },
+    resources::{
+        meyer_cross::MeyerCross,
+        profile::TrainingProfile,
+        reaction_log::{ReactionCursor, ReactionLog},
+        session_log::{SelfRating, SessionLog},
+    },
+};
+
+use super::reaction::start_session;
+
+/// Schedules the transitions the fencer rated poorly or reacted slowly to for repetition.
+pub struct RepetitionPlugin;
+
+impl Plugin for RepetitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TrainingProfile::default()).add_systems(
+            Update,
+            (
+                record_ratings,
+                record_reactions.after(start_session),
+                advance_schedule,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// The transition into the attack, as the right-handed schedule knows it.
+fn transition(from: &Attack, to: &Attack, left_handed: bool) -> Transition {
+    let transition = Transition {
+        from: from.opening,
+        to: to.opening,
+        strike: to.strike,
+    };
+    if left_handed {
+        transition.mirrored()
+    } else {
+        transition
+    }
+}
+
+fn rating_recall(rating: SelfRating) -> Recall {
+    match rating {
+        SelfRating::Good => Recall::Good,
+        SelfRating::Unsure => Recall::Hesitant,
+        SelfRating::Wrong => Recall::Failed,
+    }
+}
+
+fn record_ratings(
+    ui: Res<UiState>,
+    log: Res<SessionLog>,
+    mut profile: ResMut<TrainingProfile>,
+    mut cursor: Local<usize>,
+) {
+    let new_entries = log.read_new(&mut cursor);
+    if !ui.spaced_repetition {
+        return;
+    }
+
+    for entry in new_entries {
+        for pair in entry.attacks.windows(2) {
+            if let Some(rating) = pair[1].rating {
+                let transition = transition(&pair[0].attack, &pair[1].attack, ui.left_handed);
+                profile.schedule.review(transition, rating_recall(rating));
+            }
+        }
+    }
+}
+
+/// Reactions within the target time count as good, up to half again as long as hesitant.
+fn record_reactions(
+    ui: Res<UiState>,
+    log: Res<ReactionLog>,
+    mut profile: ResMut<TrainingProfile>,
+    mut cursor: Local<ReactionCursor>,
+) {
+    let (new_samples, _) = log.read_new(&mut cursor);
+    if !ui.spaced_repetition || !ui.reaction_measurement || ui.flash_mode {
+        return;
+    }
+
+    for sample in new_samples {
+        let Some(previous) = &sample.previous else {
+            continue;
+        };
+        let recall = if sample.latency_s <= ui.reaction_target_s {
+            Recall::Good
+        } else if sample.latency_s <= ui.reaction_target_s * 1.5 {
+            Recall::Hesitant
+        } else {
+            Recall::Failed
+        };
+        let transition = transition(previous, &sample.attack, ui.left_handed);
+        profile.schedule.review(transition, recall);
+    }
+}
+
+/// Counts drills on the schedule's clock and keeps the profile saved.
+fn advance_schedule(
+    ui: Res<UiState>,
+    meyer_cross: Res<MeyerCross>,
+    mut profile: ResMut<TrainingProfile>,
+) {
+    if !ui.spaced_repetition || !meyer_cross.is_changed() || meyer_cross.is_added() {
+        return;
+    }
+    profile.schedule.advance();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Err(e) = profile.save() {
+        warn!("Training profile not saved: {e}");
+    }
+}
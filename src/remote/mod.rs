@@ -0,0 +1,460 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use tungstenite::{handshake::derive_accept_key, protocol::Role, Message, WebSocket};
+
+use crate::meyer_cross::{
+    rules::DrillRules,
+    share::{decode_rules, encode_rules, ShareLink},
+    MeyerCross,
+};
+
+/// Only reachable from this computer unless the network is opted into.
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:8347";
+
+/// Listens on all interfaces so the coach's phone in the same network can connect.
+pub const LAN_ADDRESS: &str = "0.0.0.0:8347";
+
+const PAGE: &str = include_str!("page.html");
+
+/// Largest request body accepted, commands and rules are far smaller.
+const MAX_BODY: usize = 4096;
+
+/// Connections served at once, each WebSocket stays open for as long as its page.
+const MAX_CONNECTIONS: usize = 16;
+
+/// How long a client may take to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often WebSocket clients look for a new drill.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// What the remote asks the app to do.
+#[derive(Clone, PartialEq, Debug)]
+pub enum RemoteCommand {
+    Regenerate,
+    Pause,
+    Resume,
+    Rules(Box<DrillRules>),
+}
+
+impl RemoteCommand {
+    /// Parses a command like `regenerate` or `rules s3_ls_4-6_`, the argument is the rules
+    /// encoding of drill links.
+    pub fn parse(command: &str, argument: &str) -> Option<Self> {
+        match command {
+            "regenerate" => Some(RemoteCommand::Regenerate),
+            "pause" => Some(RemoteCommand::Pause),
+            "resume" => Some(RemoteCommand::Resume),
+            "rules" => decode_rules(argument.trim())
+                .ok()
+                .map(|rules| RemoteCommand::Rules(Box::new(rules))),
+            _ => None,
+        }
+    }
+}
+
+/// The state shown to remotes, as JSON with a version that increases on every update.
+#[derive(Default)]
+struct Snapshot {
+    version: u64,
+    json: String,
+}
+
+/// Describes the drill for the remote page.
+pub fn drill_json(meyer_cross: &MeyerCross, rules: &DrillRules, paused: bool) -> String {
+    let attacks = meyer_cross
+        .attack_sequence
+        .iter()
+        .flatten()
+        .map(|attack| {
+            format!(
+                r#"{{"opening":"{}","strike":"{}","target":{},"response":{}}}"#,
+                attack.opening,
+                attack.strike,
+                attack
+                    .target
+                    .map_or("null".to_string(), |t| format!(r#""{t}""#)),
+                attack
+                    .response
+                    .map_or("null".to_string(), |r| format!(r#""{r}""#))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let link = ShareLink {
+        drill: meyer_cross.clone(),
+        rules: None,
+        seed: None,
+    };
+    format!(
+        r#"{{"preparation":{},"attacks":[{attacks}],"rules":"{}","paused":{paused},"link":"{}"}}"#,
+        meyer_cross
+            .preparation
+            .map_or("null".to_string(), |p| format!(r#""{p}""#)),
+        encode_rules(rules),
+        link.encode(),
+    )
+}
+
+/// Embedded HTTP and WebSocket server, runs on its own threads and hands the commands over.
+/// Every request must carry the server's random token, so other web pages the coach opens
+/// cannot control the drills.
+pub struct RemoteServer {
+    address: SocketAddr,
+    token: String,
+    commands: Mutex<Receiver<RemoteCommand>>,
+    snapshot: Arc<Mutex<Snapshot>>,
+}
+
+impl RemoteServer {
+    pub fn start(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let (sender, receiver) = channel();
+        let snapshot = Arc::new(Mutex::new(Snapshot::default()));
+        let token = format!("{:016x}", rand::random::<u64>());
+
+        let shared = snapshot.clone();
+        let expected_token = token.clone();
+        thread::spawn(move || {
+            let open = Arc::new(AtomicUsize::new(0));
+            for mut stream in listener.incoming().flatten() {
+                let Some(slot) = ConnectionSlot::take(&open) else {
+                    let _ = respond(
+                        &mut stream,
+                        "503 Service Unavailable",
+                        "text/plain",
+                        "Too many connections",
+                    );
+                    continue;
+                };
+                let sender = sender.clone();
+                let snapshot = shared.clone();
+                let token = expected_token.clone();
+                thread::spawn(move || {
+                    let _ = handle_connection(stream, &token, &sender, &snapshot);
+                    drop(slot);
+                });
+            }
+        });
+
+        Ok(RemoteServer {
+            address,
+            token,
+            commands: Mutex::new(receiver),
+            snapshot,
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The link to the control page, including the token.
+    pub fn url(&self) -> String {
+        format!("http://{}/?token={}", self.address, self.token)
+    }
+
+    /// The commands received since the last call.
+    pub fn commands(&self) -> Vec<RemoteCommand> {
+        self.commands
+            .lock()
+            .map(|receiver| receiver.try_iter().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn publish(&self, json: String) {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            snapshot.version += 1;
+            snapshot.json = json;
+        }
+    }
+}
+
+/// Counts an open connection until it is dropped.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    /// `None` if [`MAX_CONNECTIONS`] are open already.
+    fn take(open: &Arc<AtomicUsize>) -> Option<Self> {
+        open.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+            (count < MAX_CONNECTIONS).then_some(count + 1)
+        })
+        .ok()
+        .map(|_| ConnectionSlot(open.clone()))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    websocket_key: Option<String>,
+    /// The body, or `None` if it is larger than [`MAX_BODY`] and was not read.
+    body: Option<String>,
+}
+
+fn read_request(reader: &mut BufReader<TcpStream>) -> io::Result<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    let mut websocket_key = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => content_length = value.trim().parse().unwrap_or(0),
+                "sec-websocket-key" => websocket_key = Some(value.trim().to_string()),
+                _ => (),
+            }
+        }
+    }
+
+    let mut body = vec![];
+    if content_length <= MAX_BODY {
+        reader
+            .by_ref()
+            .take(content_length as u64)
+            .read_to_end(&mut body)?;
+    }
+    Ok(Request {
+        method,
+        path,
+        websocket_key,
+        body: (content_length <= MAX_BODY).then(|| String::from_utf8_lossy(&body).into_owned()),
+    })
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    token: &str,
+    commands: &Sender<RemoteCommand>,
+    snapshot: &Mutex<Snapshot>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let request = read_request(&mut reader)?;
+    let mut stream = stream;
+
+    let (path, query) = request.path.split_once('?').unwrap_or((&request.path, ""));
+    let authorized = query
+        .split('&')
+        .any(|pair| pair.split_once('=') == Some(("token", token)));
+    if !authorized {
+        return respond(&mut stream, "403 Forbidden", "text/plain", "Invalid token");
+    }
+
+    if let Some(key) = request.websocket_key {
+        write!(
+            stream,
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Accept: {}\r\n\r\n",
+            derive_accept_key(key.as_bytes())
+        )?;
+        return serve_websocket(stream, commands, snapshot);
+    }
+
+    let Some(body) = request.body else {
+        return respond(
+            &mut stream,
+            "413 Payload Too Large",
+            "text/plain",
+            "Request too large",
+        );
+    };
+    let current = || snapshot.lock().map(|s| s.json.clone()).unwrap_or_default();
+    match (request.method.as_str(), path) {
+        ("GET", "/") => respond(&mut stream, "200 OK", "text/html; charset=utf-8", PAGE),
+        ("GET", "/drill") => respond(&mut stream, "200 OK", "application/json", &current()),
+        ("POST", path) => match RemoteCommand::parse(path.trim_start_matches('/'), &body) {
+            Some(command) => {
+                let _ = commands.send(command);
+                respond(&mut stream, "202 Accepted", "text/plain", "")
+            }
+            None => respond(
+                &mut stream,
+                "400 Bad Request",
+                "text/plain",
+                "Unknown command",
+            ),
+        },
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "Not found"),
+    }
+}
+
+/// Takes commands as text messages and pushes every new drill to the client.
+fn serve_websocket(
+    stream: TcpStream,
+    commands: &Sender<RemoteCommand>,
+    snapshot: &Mutex<Snapshot>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(POLL_INTERVAL))?;
+    let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+    let mut sent_version = 0;
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                let (command, argument) = text.split_once(' ').unwrap_or((&text, ""));
+                if let Some(command) = RemoteCommand::parse(command, argument) {
+                    let _ = commands.send(command);
+                }
+            }
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => (),
+            Err(tungstenite::Error::Io(e))
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            Err(_) => return Ok(()),
+        }
+
+        let update = snapshot
+            .lock()
+            .ok()
+            .filter(|s| s.version != sent_version)
+            .map(|s| (s.version, s.json.clone()));
+        if let Some((version, json)) = update {
+            sent_version = version;
+            if socket.send(Message::Text(json)).is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw_request(server: &RemoteServer, text: &str) -> String {
+        let mut stream = TcpStream::connect(server.address()).unwrap();
+        stream.write_all(text.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    /// Sends e.g. `GET /drill` with the server's token and the rest of the request.
+    fn request(server: &RemoteServer, target: &str, rest: &str) -> String {
+        let text = format!("{target}?token={} HTTP/1.1\r\n{rest}", server.token);
+        raw_request(server, &text)
+    }
+
+    #[test]
+    fn serves_drill_and_takes_commands_over_http() {
+        let server = RemoteServer::start("127.0.0.1:0").unwrap();
+        let rules = DrillRules::default();
+        let drill = MeyerCross::random(&mut rand::thread_rng(), &rules).unwrap();
+        server.publish(drill_json(&drill, &rules, false));
+
+        let response = request(&server, "GET /drill", "\r\n");
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains(r#""preparation":"Streichen 3x""#));
+        let response = raw_request(&server, "POST /regenerate HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 403"));
+        let response = raw_request(&server, "POST /regenerate?token=0 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 403"));
+
+        let response = request(
+            &server,
+            "POST /rules",
+            "Content-Length: 11\r\n\r\ns3_ls_4-6_d",
+        );
+        assert!(response.starts_with("HTTP/1.1 202"));
+        request(&server, "POST /pause", "\r\n");
+        let response = request(
+            &server,
+            "POST /rules",
+            "Content-Length: 18446744073709551615\r\n\r\n",
+        );
+        assert!(response.starts_with("HTTP/1.1 413"));
+        assert!(request(&server, "POST /jump", "\r\n").starts_with("HTTP/1.1 400"));
+
+        let commands = server.commands();
+        let RemoteCommand::Rules(received) = &commands[0] else {
+            panic!("expected rules, got {commands:?}");
+        };
+        assert_eq!((received.min_length, received.max_length), (4, 6));
+        assert!(received.doppelfehler_enabled);
+        assert_eq!(commands[1], RemoteCommand::Pause);
+    }
+
+    #[test]
+    fn refuses_connections_over_the_limit() {
+        let server = RemoteServer::start("127.0.0.1:0").unwrap();
+        let idle = (0..MAX_CONNECTIONS)
+            .map(|_| TcpStream::connect(server.address()).unwrap())
+            .collect::<Vec<_>>();
+        // Refused before the request is read.
+        let mut refused = TcpStream::connect(server.address()).unwrap();
+        let mut response = String::new();
+        refused.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 503"), "{response}");
+
+        drop(idle);
+        let served = (0..20).any(|_| {
+            thread::sleep(POLL_INTERVAL);
+            request(&server, "GET /drill", "\r\n").starts_with("HTTP/1.1 200")
+        });
+        assert!(served);
+    }
+
+    #[test]
+    fn pushes_drills_and_takes_commands_over_websocket() {
+        let server = RemoteServer::start("127.0.0.1:0").unwrap();
+        server.publish("{}".to_string());
+
+        let stream = TcpStream::connect(server.address()).unwrap();
+        let url = format!("ws://{}/ws?token={}", server.address(), server.token);
+        let (mut socket, _) = tungstenite::client(url, stream).unwrap();
+        assert_eq!(socket.read().unwrap(), Message::Text("{}".to_string()));
+
+        socket
+            .send(Message::Text("regenerate".to_string()))
+            .unwrap();
+        server.publish(r#"{"paused":true}"#.to_string());
+        assert_eq!(
+            socket.read().unwrap(),
+            Message::Text(r#"{"paused":true}"#.to_string())
+        );
+        let commands = (0..20)
+            .map(|_| {
+                thread::sleep(POLL_INTERVAL);
+                server.commands()
+            })
+            .find(|commands| !commands.is_empty());
+        assert_eq!(commands, Some(vec![RemoteCommand::Regenerate]));
+    }
+}
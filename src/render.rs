@@ -0,0 +1,64 @@
+use std::fmt;
+
+use crate::meyer_cross::types::Strike;
+
+/// Non-color cue telling the strikes apart.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum StrikeGlyph {
+    #[default]
+    None,
+    Letter,
+    Brackets,
+}
+
+impl StrikeGlyph {
+    pub const ALL: [StrikeGlyph; 3] = [
+        StrikeGlyph::None,
+        StrikeGlyph::Letter,
+        StrikeGlyph::Brackets,
+    ];
+
+    pub fn label(&self, number: usize, strike: Strike) -> String {
+        match self {
+            StrikeGlyph::None => number.to_string(),
+            StrikeGlyph::Letter => format!("{number}{}", strike_letter(strike)),
+            StrikeGlyph::Brackets => match strike {
+                Strike::Long => number.to_string(),
+                Strike::Short => format!("({number})"),
+                Strike::Flat => format!("[{number}]"),
+                Strike::Fehler => format!("<{number}>"),
+            },
+        }
+    }
+}
+
+pub fn strike_letter(strike: Strike) -> &'static str {
+    match strike {
+        Strike::Long => "L",
+        Strike::Short => "S",
+        Strike::Flat => "F",
+        Strike::Fehler => "X",
+    }
+}
+
+impl fmt::Display for StrikeGlyph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StrikeGlyph::None => "Color Only",
+            StrikeGlyph::Letter => "Letter Suffix",
+            StrikeGlyph::Brackets => "Brackets",
+        })
+    }
+}
+
+/// Share of the way from the center to the feinted opening where a Fehler turns to its target.
+pub const FEHLER_SPLIT: f32 = 0.6;
+
+/// Start and end, as shares of the line, of the dashes of a dashed line.
+pub fn dashes(length: f32, dash: f32) -> Vec<(f32, f32)> {
+    let count = (length / (2.0 * dash)).ceil().max(1.0) as usize;
+    let step = 1.0 / count as f32;
+    (0..count)
+        .map(|i| (i as f32 * step, (i as f32 + 0.5) * step))
+        .collect()
+}
@@ -1 +1,131 @@
+use std::time::Duration;
 
+use bevy::prelude::*;
+use rand::Rng;
+
+/// How long to wait before the next attack is cued.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CueTiming {
+    Fixed(f32),
+    Random { min_s: f32, max_s: f32 },
+}
+
+impl CueTiming {
+    pub fn next_delay(&self) -> Duration {
+        match *self {
+            CueTiming::Fixed(delay_s) => Duration::from_secs_f32(delay_s),
+            CueTiming::Random { min_s, max_s } => {
+                Duration::from_secs_f32(rand::thread_rng().gen_range(min_s..=max_s.max(min_s)))
+            }
+        }
+    }
+}
+
+/// Sent whenever the countdown reveals the attack at `index`.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct AttackCued {
+    pub index: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum CountdownPhase {
+    #[default]
+    Inactive,
+    Preparation,
+    Attacks,
+    Done,
+}
+
+/// Reveals the attacks of the current drill one by one while the timer is active.
+#[derive(Resource, Default)]
+pub struct Countdown {
+    phase: CountdownPhase,
+    timer: Timer,
+    revealed: usize,
+    paused: bool,
+    following: bool,
+}
+
+impl Countdown {
+    pub fn start(&mut self, preparation_s: f32) {
+        self.phase = CountdownPhase::Preparation;
+        self.timer = Timer::from_seconds(preparation_s, TimerMode::Once);
+        self.revealed = 0;
+    }
+
+    pub fn stop(&mut self) {
+        *self = Countdown {
+            paused: self.paused,
+            ..Self::default()
+        };
+    }
+
+    /// Takes over the state of another countdown, e.g. the host of a group drill. The own
+    /// timer stays halted until [`Countdown::stop`].
+    pub fn follow(&mut self, phase: CountdownPhase, revealed: usize) {
+        self.phase = phase;
+        self.revealed = revealed;
+        self.following = true;
+    }
+
+    pub fn is_following(&self) -> bool {
+        self.following
+    }
+
+    pub fn phase(&self) -> CountdownPhase {
+        self.phase
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.phase != CountdownPhase::Inactive
+    }
+
+    pub fn revealed(&self) -> usize {
+        self.revealed
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Holds the countdown at the current attack, it survives restarts until resumed.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// The Streichen being counted out, 1 to `count`, while the preparation time runs.
+    pub fn streichen_stroke(&self, count: usize) -> Option<usize> {
+        (self.phase == CountdownPhase::Preparation && count > 0)
+            .then(|| ((self.timer.percent() * count as f32) as usize + 1).min(count))
+    }
+
+    pub fn is_revealed(&self, index: usize) -> bool {
+        !self.is_active() || index < self.revealed
+    }
+
+    /// Advances the countdown and returns true once the last attack had its time.
+    pub fn tick(&mut self, delta: Duration, attack_count: usize, cue_timing: CueTiming) -> bool {
+        if self.paused
+            || self.following
+            || !matches!(
+                self.phase,
+                CountdownPhase::Preparation | CountdownPhase::Attacks
+            )
+            || !self.timer.tick(delta).just_finished()
+        {
+            return false;
+        }
+
+        if self.phase == CountdownPhase::Attacks && self.revealed >= attack_count {
+            self.phase = CountdownPhase::Done;
+            return true;
+        }
+
+        if self.phase == CountdownPhase::Attacks {
+            self.revealed += 1;
+        }
+        self.phase = CountdownPhase::Attacks;
+        self.timer = Timer::new(cue_timing.next_delay(), TimerMode::Once);
+        false
+    }
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Source of randomness for drill generation, seeded so a shared seed reproduces the drills.
+#[derive(Resource)]
+pub struct DrillRng {
+    rng: StdRng,
+}
+
+impl Default for DrillRng {
+    fn default() -> Self {
+        Self::from_seed(rand::thread_rng().gen())
+    }
+}
+
+impl DrillRng {
+    pub fn from_seed(seed: u64) -> Self {
+        DrillRng {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::from_seed(seed);
+    }
+
+    /// Continues with a new seed drawn from the current state, sharing it reproduces the drills
+    /// from here on rather than from the start of the session.
+    pub fn fresh_seed(&mut self) -> u64 {
+        let seed = self.rng.gen();
+        self.reseed(seed);
+        seed
+    }
+
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+}
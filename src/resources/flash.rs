@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::session_log::SelfRating;
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum FlashPhase {
+    #[default]
+    Inactive,
+    Showing,
+    Recall,
+    Review,
+}
+
+/// Shows the whole drill for a limited time, hides it while the fencer executes from memory
+/// and reveals it again for self-check.
+#[derive(Resource, Default)]
+pub struct Flash {
+    phase: FlashPhase,
+    timer: Timer,
+    ratings: Vec<Option<SelfRating>>,
+}
+
+impl Flash {
+    pub fn start(&mut self, show_s: f32, attack_count: usize) {
+        self.phase = FlashPhase::Showing;
+        self.timer = Timer::from_seconds(show_s, TimerMode::Once);
+        self.ratings = vec![None; attack_count];
+    }
+
+    pub fn stop(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn phase(&self) -> FlashPhase {
+        self.phase
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.phase != FlashPhase::Inactive
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.phase == FlashPhase::Recall
+    }
+
+    pub fn remaining_s(&self) -> f32 {
+        self.timer.remaining_secs()
+    }
+
+    pub fn tick(&mut self, delta: Duration) {
+        if self.phase == FlashPhase::Showing && self.timer.tick(delta).just_finished() {
+            self.phase = FlashPhase::Recall;
+        }
+    }
+
+    pub fn reveal(&mut self) {
+        if self.phase == FlashPhase::Recall {
+            self.phase = FlashPhase::Review;
+        }
+    }
+
+    pub fn ratings(&self) -> &[Option<SelfRating>] {
+        &self.ratings
+    }
+
+    pub fn rate(&mut self, index: usize, rating: SelfRating) {
+        if let Some(r) = self.ratings.get_mut(index) {
+            *r = Some(rating);
+        }
+    }
+}
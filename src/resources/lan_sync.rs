@@ -0,0 +1,61 @@
+use std::{fmt, io};
+
+use bevy::prelude::*;
+
+use crate::lan::{LanClient, LanHost};
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum LanRole {
+    #[default]
+    Off,
+    Host,
+    Client,
+}
+
+impl LanRole {
+    pub const ALL: [LanRole; 3] = [LanRole::Off, LanRole::Host, LanRole::Client];
+}
+
+impl fmt::Display for LanRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LanRole::Off => "Off",
+            LanRole::Host => "Host",
+            LanRole::Client => "Client",
+        })
+    }
+}
+
+/// Whether this station leads a group drill, follows a host or runs on its own.
+#[derive(Resource, Default)]
+pub struct LanSync {
+    role: LanRole,
+    host: Option<LanHost>,
+    client: Option<LanClient>,
+}
+
+impl LanSync {
+    pub fn role(&self) -> LanRole {
+        self.role
+    }
+
+    /// Opens the socket for the role, on failure the station runs on its own.
+    pub fn set_role(&mut self, role: LanRole, port: u16) -> io::Result<()> {
+        *self = Self::default();
+        match role {
+            LanRole::Off => (),
+            LanRole::Host => self.host = Some(LanHost::broadcast(port)?),
+            LanRole::Client => self.client = Some(LanClient::listen(port)?),
+        }
+        self.role = role;
+        Ok(())
+    }
+
+    pub fn host(&self) -> Option<&LanHost> {
+        self.host.as_ref()
+    }
+
+    pub fn client(&self) -> Option<&LanClient> {
+        self.client.as_ref()
+    }
+}
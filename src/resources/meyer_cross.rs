@@ -1,7 +1,51 @@
 use bevy::prelude::*;
 
-pub use crate::meyer_cross::{
-    attack_sequence::AttackSequence, preparation::Preparation, MeyerCross,
+use crate::meyer_cross::{
+    attack_sequence::{AttackSequence, AttackSequences},
+    rules::DrillRules,
 };
 
+pub use crate::meyer_cross::MeyerCross;
+
 impl Resource for MeyerCross {}
+
+/// The drill following the current one, so it can be previewed in advance.
+#[derive(Resource, Default)]
+pub struct QueuedDrill {
+    pub drill: Option<MeyerCross>,
+}
+
+/// Steps through every sequence the rules allow, one per drill, as systematic curriculum.
+#[derive(Resource, Default)]
+pub struct SequenceWalk {
+    sequences: Option<AttackSequences>,
+    position: usize,
+    total: usize,
+}
+
+impl SequenceWalk {
+    /// Starts over with the sequences of the rules, or stops if they allow none.
+    pub fn restart(&mut self, rules: &DrillRules) {
+        self.sequences = rules.attack_sequences().ok();
+        self.position = 0;
+        self.total = self.sequences.as_ref().map_or(0, ExactSizeIterator::len);
+    }
+
+    /// The next sequence, after the last one the walk starts over.
+    pub fn next_sequence(&mut self, rules: &DrillRules) -> Option<AttackSequence> {
+        if self.sequences.as_ref().is_none_or(|s| s.len() == 0) {
+            self.restart(rules);
+        }
+        let sequence = self.sequences.as_mut()?.next()?;
+        self.position += 1;
+        Some(sequence)
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
@@ -1,2 +1,11 @@
 pub mod countdown;
+pub mod drill_rng;
+pub mod flash;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod lan_sync;
 pub mod meyer_cross;
+pub mod profile;
+pub mod progression;
+pub mod quiz;
+pub mod reaction_log;
+pub mod session_log;
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+pub use crate::meyer_cross::repetition::TrainingProfile;
+
+impl Resource for TrainingProfile {}
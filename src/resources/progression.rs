@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+pub use crate::meyer_cross::difficulty::Progression;
+
+impl Resource for Progression {}
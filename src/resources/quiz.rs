@@ -0,0 +1,118 @@
+use std::{collections::HashMap, fmt};
+
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::meyer_cross::types::{Guard, Opening, Strike};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum QuizItem {
+    Guard(Guard),
+    Opening(Opening),
+    Strike(Strike),
+}
+
+impl QuizItem {
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        match rng.gen_range(0..3) {
+            0 => QuizItem::Guard(rng.gen()),
+            1 => QuizItem::Opening(rng.gen()),
+            _ => QuizItem::Strike(rng.gen()),
+        }
+    }
+
+    /// Draws another item of the same kind.
+    fn sibling<R: Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        match self {
+            QuizItem::Guard(_) => QuizItem::Guard(rng.gen()),
+            QuizItem::Opening(_) => QuizItem::Opening(rng.gen()),
+            QuizItem::Strike(_) => QuizItem::Strike(rng.gen()),
+        }
+    }
+
+    fn kind_count(&self) -> usize {
+        match self {
+            QuizItem::Guard(_) => Guard::ALL.len(),
+            QuizItem::Opening(_) => Opening::ALL.len(),
+            QuizItem::Strike(_) => Strike::ALL.len(),
+        }
+    }
+}
+
+impl fmt::Display for QuizItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuizItem::Guard(guard) => write!(f, "{guard}"),
+            QuizItem::Opening(opening) => write!(f, "{opening}"),
+            QuizItem::Strike(strike) => write!(f, "{strike}"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct QuizQuestion {
+    pub item: QuizItem,
+    pub choices: Vec<QuizItem>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Accuracy {
+    pub correct: usize,
+    pub asked: usize,
+}
+
+#[derive(Resource, Default)]
+pub struct Quiz {
+    question: Option<QuizQuestion>,
+    last_answer: Option<(QuizItem, bool)>,
+    accuracy: HashMap<QuizItem, Accuracy>,
+}
+
+impl Quiz {
+    pub const CHOICES: usize = 4;
+
+    pub fn question(&mut self) -> &QuizQuestion {
+        self.question.get_or_insert_with(|| {
+            let mut rng = rand::thread_rng();
+            let item = QuizItem::random(&mut rng);
+            let mut choices = vec![item];
+            while choices.len() < Self::CHOICES.min(item.kind_count()) {
+                let choice = item.sibling(&mut rng);
+                if !choices.contains(&choice) {
+                    choices.push(choice);
+                }
+            }
+            choices.shuffle(&mut rng);
+            QuizQuestion { item, choices }
+        })
+    }
+
+    pub fn answer(&mut self, choice: QuizItem) {
+        if let Some(question) = self.question.take() {
+            let correct = question.item == choice;
+            let accuracy = self.accuracy.entry(question.item).or_default();
+            accuracy.asked += 1;
+            accuracy.correct += usize::from(correct);
+            self.last_answer = Some((question.item, correct));
+        }
+    }
+
+    pub fn last_answer(&self) -> Option<(QuizItem, bool)> {
+        self.last_answer
+    }
+
+    /// Accuracy per asked item, worst first.
+    pub fn accuracy(&self) -> Vec<(QuizItem, Accuracy)> {
+        let mut accuracy = self
+            .accuracy
+            .iter()
+            .map(|(&item, &accuracy)| (item, accuracy))
+            .collect::<Vec<_>>();
+        accuracy.sort_by(|(_, a), (_, b)| {
+            (a.correct * b.asked)
+                .cmp(&(b.correct * a.asked))
+                .then(b.asked.cmp(&a.asked))
+        });
+        accuracy
+    }
+}
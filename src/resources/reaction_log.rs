@@ -0,0 +1,147 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::meyer_cross::types::{Attack, Opening, Strike};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ReactionSample {
+    pub index: usize,
+    pub attack: Attack,
+    /// The attack before in the same drill, kept since the drill may change before confirming.
+    pub previous: Option<Attack>,
+    pub latency_s: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ReactionAverage {
+    pub average_s: f32,
+    pub count: usize,
+}
+
+/// How far a system has read the log, see `ReactionLog::read_new`.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct ReactionCursor {
+    session: usize,
+    samples: usize,
+    missed: usize,
+}
+
+/// Latencies from attack cue to the confirming key press of one training session.
+#[derive(Resource, Default)]
+pub struct ReactionLog {
+    /// Counts the sessions, so readers notice the log starting over.
+    session: usize,
+    samples: Vec<ReactionSample>,
+    pending: VecDeque<(usize, Attack, Option<Attack>, f64)>,
+    missed: usize,
+}
+
+impl ReactionLog {
+    pub const HISTOGRAM_BIN_S: f32 = 0.1;
+    pub const HISTOGRAM_BINS: usize = 20;
+
+    pub fn cue(&mut self, index: usize, attack: Attack, previous: Option<Attack>, at_s: f64) {
+        self.pending.push_back((index, attack, previous, at_s));
+    }
+
+    /// Assigns a key press to the oldest unconfirmed cue.
+    pub fn confirm(&mut self, at_s: f64) -> Option<ReactionSample> {
+        let (index, attack, previous, cued_at_s) = self.pending.pop_front()?;
+        let sample = ReactionSample {
+            index,
+            attack,
+            previous,
+            latency_s: (at_s - cued_at_s) as f32,
+        };
+        self.samples.push(sample);
+        Some(sample)
+    }
+
+    /// Counts all unconfirmed cues as missed, e.g. when the next drill starts.
+    pub fn drop_pending(&mut self) {
+        self.missed += self.pending.len();
+        self.pending.clear();
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self {
+            session: self.session + 1,
+            ..Self::default()
+        };
+    }
+
+    /// Samples and missed cues since the cursor, which then moves past them. After the log
+    /// started over the cursor reads it from the start.
+    pub fn read_new(&self, cursor: &mut ReactionCursor) -> (&[ReactionSample], usize) {
+        if cursor.session != self.session {
+            *cursor = ReactionCursor {
+                session: self.session,
+                ..default()
+            };
+        }
+        let samples = self.samples.get(cursor.samples..).unwrap_or_default();
+        let missed = self.missed.saturating_sub(cursor.missed);
+        cursor.samples = self.samples.len();
+        cursor.missed = self.missed;
+        (samples, missed)
+    }
+
+    pub fn missed(&self) -> usize {
+        self.missed
+    }
+
+    pub fn average(&self) -> Option<ReactionAverage> {
+        self.average_where(|_| true)
+    }
+
+    pub fn average_by_index(&self) -> Vec<(usize, ReactionAverage)> {
+        let max_index = self.samples.iter().map(|s| s.index).max();
+        max_index.map_or(vec![], |max_index| {
+            (0..=max_index)
+                .filter_map(|i| Some((i, self.average_where(|s| s.index == i)?)))
+                .collect()
+        })
+    }
+
+    pub fn average_by_strike(&self) -> Vec<(Strike, ReactionAverage)> {
+        Strike::ALL
+            .iter()
+            .filter_map(|&k| Some((k, self.average_where(|s| s.attack.strike == k)?)))
+            .collect()
+    }
+
+    pub fn average_by_opening(&self) -> Vec<(Opening, ReactionAverage)> {
+        Opening::ALL
+            .iter()
+            .filter_map(|&k| Some((k, self.average_where(|s| s.attack.opening == k)?)))
+            .collect()
+    }
+
+    /// Sample counts in bins of `HISTOGRAM_BIN_S`, the last bin collects all slower reactions.
+    pub fn histogram(&self) -> [usize; Self::HISTOGRAM_BINS] {
+        let mut bins = [0; Self::HISTOGRAM_BINS];
+        for sample in &self.samples {
+            let bin = (sample.latency_s.max(0.0) / Self::HISTOGRAM_BIN_S) as usize;
+            bins[bin.min(Self::HISTOGRAM_BINS - 1)] += 1;
+        }
+        bins
+    }
+
+    fn average_where(&self, filter: impl Fn(&ReactionSample) -> bool) -> Option<ReactionAverage> {
+        let latencies = self
+            .samples
+            .iter()
+            .filter(|s| filter(s))
+            .map(|s| s.latency_s)
+            .collect::<Vec<_>>();
+        if latencies.is_empty() {
+            None
+        } else {
+            Some(ReactionAverage {
+                average_s: latencies.iter().sum::<f32>() / latencies.len() as f32,
+                count: latencies.len(),
+            })
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+use crate::meyer_cross::{preparation::Preparation, types::Attack};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SelfRating {
+    Good,
+    Unsure,
+    Wrong,
+}
+
+impl SelfRating {
+    pub const ALL: [SelfRating; 3] = [SelfRating::Good, SelfRating::Unsure, SelfRating::Wrong];
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RatedAttack {
+    pub attack: Attack,
+    pub rating: Option<SelfRating>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SessionEntry {
+    pub preparation: Option<Preparation>,
+    pub attacks: Vec<RatedAttack>,
+}
+
+/// All drills executed during the current session.
+#[derive(Resource, Default)]
+pub struct SessionLog {
+    entries: Vec<SessionEntry>,
+}
+
+impl SessionLog {
+    pub fn record(&mut self, entry: SessionEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn entries(&self) -> &[SessionEntry] {
+        &self.entries
+    }
+
+    /// Entries recorded since the cursor, which then moves past them.
+    pub fn read_new(&self, cursor: &mut usize) -> &[SessionEntry] {
+        let entries = self.entries.get(*cursor..).unwrap_or_default();
+        *cursor = self.entries.len();
+        entries
+    }
+
+    pub fn rating_count(&self, rating: SelfRating) -> usize {
+        self.entries
+            .iter()
+            .flat_map(|e| e.attacks.iter())
+            .filter(|a| a.rating == Some(rating))
+            .count()
+    }
+}